@@ -0,0 +1,61 @@
+use std::env;
+use std::process::Command;
+
+// The GEOS releases whose additions are gated, with the cfg set when the installed
+// GEOS is at least that version (or when the matching cargo feature is enabled).
+const VERSIONS: [(u32, u32, &str); 4] = [
+    (3, 7, "v3_7_0"),
+    (3, 8, "v3_8_0"),
+    (3, 9, "v3_9_0"),
+    (3, 10, "v3_10_0"),
+];
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// Parses the major and minor numbers of a version such as "3.10.2" or "3.11.0dev".
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut numbers = version.trim().split('.');
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers
+        .next()?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    Some((major, minor))
+}
+
+fn detect_version() -> Option<(u32, u32)> {
+    run("geos-config", &["--version"])
+        .or_else(|| run("pkg-config", &["--modversion", "geos"]))
+        .and_then(|version| parse_version(&version))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=PATH");
+    println!("cargo:rerun-if-env-changed=PKG_CONFIG_PATH");
+
+    let detected = detect_version();
+    if detected.is_none() {
+        println!(
+            "cargo:warning=could not find the GEOS version with geos-config or pkg-config, \
+             only the enabled v3_x_0 features are used"
+        );
+    }
+
+    for &(major, minor, name) in VERSIONS.iter() {
+        println!("cargo:rustc-check-cfg=cfg(geos_{})", name);
+        let feature = env::var_os(format!("CARGO_FEATURE_{}", name.to_uppercase())).is_some();
+        if feature || detected.is_some_and(|version| version >= (major, minor)) {
+            println!("cargo:rustc-cfg=geos_{}", name);
+        }
+    }
+}
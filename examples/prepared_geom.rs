@@ -1,13 +1,13 @@
 extern crate geos;
-use geos::{version, GGeom, PreparedGGeom};
+use geos::{version, GGeom, GeosError, PreparedGGeom};
 
-fn main() {
+fn main() -> Result<(), GeosError> {
     println!("geos_c version: {}", version());
-    let g1 = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))");
-    let g2 = GGeom::new("POLYGON ((1 1, 1 3, 5 5, 5 0, 1 1))");
-    let pg1 = PreparedGGeom::new(&g1);
-    let result = pg1.intersects(&g2);
-    let result2 = pg1.contains(&g2.get_centroid());
+    let g1 = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))")?;
+    let g2 = GGeom::new("POLYGON ((1 1, 1 3, 5 5, 5 0, 1 1))")?;
+    let pg1 = PreparedGGeom::new(&g1)?;
+    let result = pg1.intersects(&g2)?;
+    let result2 = pg1.contains(&g2.get_centroid()?)?;
     println!("Prepared geometry intersects test polygon : {:?}", result);
     println!(
         "Prepared geometry contains centroid other polygon : {:?}",
@@ -15,13 +15,14 @@ fn main() {
     );
     println!("Prepared geometry intersects each geometry from a vec of GeosGeometry :");
     let vec_geoms = vec![
-        GGeom::new("POINT (1.3 2.4)"),
-        GGeom::new("POINT (2.1 0.3)"),
-        GGeom::new("POINT (3.1 4.7)"),
-        GGeom::new("POINT (0.4 4.1)"),
+        GGeom::new("POINT (1.3 2.4)")?,
+        GGeom::new("POINT (2.1 0.3)")?,
+        GGeom::new("POINT (3.1 4.7)")?,
+        GGeom::new("POINT (0.4 4.1)")?,
     ];
     for geom in &vec_geoms {
-        print!("{:?} ", pg1.intersects(&geom));
+        print!("{:?} ", pg1.intersects(&geom)?);
     }
     println!("");
+    Ok(())
 }
@@ -0,0 +1,107 @@
+use libc::{c_double, c_int};
+use context_handle::{handle, last_error, with_drop_handle};
+use error::GeosError;
+use ffi::{GEOSBufferParams, GEOSBufferParams_create_r, GEOSBufferParams_destroy_r,
+          GEOSBufferParams_setEndCapStyle_r, GEOSBufferParams_setJoinStyle_r,
+          GEOSBufferParams_setMitreLimit_r, GEOSBufferParams_setQuadrantSegments_r,
+          GEOSBufferParams_setSingleSided_r};
+
+/// Shape of the buffer at the ends of the lines.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CapStyle {
+    Round = 1,
+    Flat = 2,
+    Square = 3,
+}
+
+/// Shape of the buffer at the corners of the lines and polygons.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum JoinStyle {
+    Round = 1,
+    Mitre = 2,
+    Bevel = 3,
+}
+
+/// Parameters of `GGeom::buffer_with_params`.
+///
+/// ```no_run
+/// # use geos::{BufferParams, CapStyle, GGeom, GeosError, JoinStyle};
+/// # fn main() -> Result<(), GeosError> {
+/// let params = BufferParams::new()?
+///     .end_cap_style(CapStyle::Flat)?
+///     .join_style(JoinStyle::Mitre)?
+///     .single_sided(true)?;
+/// let road = GGeom::new("LINESTRING (0 0, 10 0, 10 10)")?;
+/// let left_side = road.buffer_with_params(&params, 2.)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct BufferParams(pub(crate) *mut GEOSBufferParams);
+
+unsafe impl Send for BufferParams {}
+
+impl Drop for BufferParams {
+    fn drop(&mut self) {
+        with_drop_handle(|handle| unsafe { GEOSBufferParams_destroy_r(handle, self.0) });
+    }
+}
+
+fn check_setter(ret_val: c_int, func: &str) -> Result<(), GeosError> {
+    if ret_val == 0 {
+        return Err(last_error(GeosError::InvalidArgument(format!(
+            "{} failed",
+            func
+        ))));
+    }
+    Ok(())
+}
+
+impl BufferParams {
+    pub fn new() -> Result<BufferParams, GeosError> {
+        let params = unsafe { GEOSBufferParams_create_r(handle()) };
+        if params.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSBufferParams_create returned NULL".to_string(),
+            )));
+        }
+        Ok(BufferParams(params))
+    }
+
+    pub fn end_cap_style(self, style: CapStyle) -> Result<BufferParams, GeosError> {
+        let ret_val =
+            unsafe { GEOSBufferParams_setEndCapStyle_r(handle(), self.0, style as c_int) };
+        check_setter(ret_val, "GEOSBufferParams_setEndCapStyle")?;
+        Ok(self)
+    }
+
+    pub fn join_style(self, style: JoinStyle) -> Result<BufferParams, GeosError> {
+        let ret_val = unsafe { GEOSBufferParams_setJoinStyle_r(handle(), self.0, style as c_int) };
+        check_setter(ret_val, "GEOSBufferParams_setJoinStyle")?;
+        Ok(self)
+    }
+
+    /// Limits the length of the mitre joins, as a ratio of the buffer width.
+    pub fn mitre_limit(self, limit: f64) -> Result<BufferParams, GeosError> {
+        let ret_val =
+            unsafe { GEOSBufferParams_setMitreLimit_r(handle(), self.0, limit as c_double) };
+        check_setter(ret_val, "GEOSBufferParams_setMitreLimit")?;
+        Ok(self)
+    }
+
+    /// Sets the number of segments used to approximate a quarter circle.
+    pub fn quadrant_segments(self, quadsegs: i32) -> Result<BufferParams, GeosError> {
+        let ret_val =
+            unsafe { GEOSBufferParams_setQuadrantSegments_r(handle(), self.0, quadsegs as c_int) };
+        check_setter(ret_val, "GEOSBufferParams_setQuadrantSegments")?;
+        Ok(self)
+    }
+
+    /// Buffers lines on one side only: the left side for a positive width,
+    /// the right side for a negative one.
+    pub fn single_sided(self, single_sided: bool) -> Result<BufferParams, GeosError> {
+        let ret_val =
+            unsafe { GEOSBufferParams_setSingleSided_r(handle(), self.0, single_sided as c_int) };
+        check_setter(ret_val, "GEOSBufferParams_setSingleSided")?;
+        Ok(self)
+    }
+}
@@ -0,0 +1,173 @@
+use libc::{c_char, c_void};
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::RwLock;
+use error::GeosError;
+use ffi::{GEOSContextHandle_t, GEOSContext_setErrorMessageHandler_r,
+          GEOSContext_setNoticeMessageHandler_r, GEOS_finish_r, GEOS_init_r};
+
+type NoticeHandler = Box<dyn Fn(&str) + Send + Sync>;
+type ContextNoticeHandler = Box<dyn Fn(&str)>;
+
+static NOTICE_HANDLER: RwLock<Option<NoticeHandler>> = RwLock::new(None);
+
+thread_local!(
+    static CONTEXT: GContextHandle =
+        GContextHandle::new().expect("Unable to create a GEOS context handle")
+);
+
+struct Messages {
+    last_error: RefCell<Option<String>>,
+    last_notice: RefCell<Option<String>>,
+    notice_handler: RefCell<Option<ContextNoticeHandler>>,
+}
+
+/// A GEOS context, as used by the reentrant (`*_r`) functions of the C API.
+///
+/// Every thread lazily creates its own context the first time it calls GEOS,
+/// so geometries can be moved across threads without a context ever being
+/// used by two threads at once. Use `with_context` to access the context of
+/// the current thread.
+pub struct GContextHandle {
+    ptr: GEOSContextHandle_t,
+    messages: Box<Messages>,
+}
+
+impl GContextHandle {
+    fn new() -> Result<GContextHandle, GeosError> {
+        let ptr = unsafe { GEOS_init_r() };
+        if ptr.is_null() {
+            return Err(GeosError::NullReturn(
+                "GEOS_init_r returned NULL".to_string(),
+            ));
+        }
+        let messages = Box::new(Messages {
+            last_error: RefCell::new(None),
+            last_notice: RefCell::new(None),
+            notice_handler: RefCell::new(None),
+        });
+        // The messages are boxed so their address stays valid as user data.
+        let user_data = &*messages as *const Messages as *mut c_void;
+        unsafe {
+            GEOSContext_setNoticeMessageHandler_r(ptr, Some(notice_handler), user_data);
+            GEOSContext_setErrorMessageHandler_r(ptr, Some(error_handler), user_data);
+        }
+        Ok(GContextHandle { ptr, messages })
+    }
+
+    /// Sets the function receiving the notice messages emitted by GEOS in this
+    /// context, taking precedence over the handler given to `set_notice_handler`.
+    pub fn set_notice_message_handler(&self, handler: Option<ContextNoticeHandler>) {
+        *self.messages.notice_handler.borrow_mut() = handler;
+    }
+
+    /// Returns the error message emitted by the last GEOS call of this context,
+    /// unless it was already attached to a returned `GeosError`.
+    pub fn get_last_error(&self) -> Option<String> {
+        self.messages.last_error.borrow().clone()
+    }
+
+    /// Returns the last notice message emitted by GEOS in this context.
+    pub fn get_last_notice(&self) -> Option<String> {
+        self.messages.last_notice.borrow().clone()
+    }
+}
+
+impl Drop for GContextHandle {
+    fn drop(&mut self) {
+        unsafe { GEOS_finish_r(self.ptr) };
+    }
+}
+
+/// Calls `f` with the GEOS context of the current thread.
+pub fn with_context<F, R>(f: F) -> R
+where
+    F: FnOnce(&GContextHandle) -> R,
+{
+    CONTEXT.with(f)
+}
+
+/// Sets the function receiving the notice messages emitted by GEOS
+/// (for example to forward them to a logger), in every thread.
+pub fn set_notice_handler<F>(handler: F)
+where
+    F: Fn(&str) + Send + Sync + 'static,
+{
+    if let Ok(mut notice_handler) = NOTICE_HANDLER.write() {
+        *notice_handler = Some(Box::new(handler));
+    }
+}
+
+/// Removes the notice handler, notice messages emitted by GEOS are then discarded.
+pub fn remove_notice_handler() {
+    if let Ok(mut notice_handler) = NOTICE_HANDLER.write() {
+        *notice_handler = None;
+    }
+}
+
+/// Returns the context of the current thread, to pass to a GEOS call.
+///
+/// The last error message is cleared, so that a failure which wasn't turned into
+/// a `GeosError` can't be reported again by the next failing call.
+pub fn handle() -> GEOSContextHandle_t {
+    CONTEXT.with(|context| {
+        *context.messages.last_error.borrow_mut() = None;
+        context.ptr
+    })
+}
+
+/// Calls `f` with a context to destroy a GEOS object.
+///
+/// The context of the current thread is already gone when the object is dropped
+/// by a thread-local destructor running after the one of the context, a temporary
+/// context is then used instead.
+pub(crate) fn with_drop_handle<F>(f: F)
+where
+    F: FnOnce(GEOSContextHandle_t),
+{
+    if let Ok(ptr) = CONTEXT.try_with(|context| context.ptr) {
+        return f(ptr);
+    }
+    let ptr = unsafe { GEOS_init_r() };
+    if !ptr.is_null() {
+        f(ptr);
+        unsafe { GEOS_finish_r(ptr) };
+    }
+}
+
+/// Builds the error of a failed GEOS call, using the message GEOS reported if any.
+pub fn last_error(fallback: GeosError) -> GeosError {
+    match CONTEXT.with(|context| context.messages.last_error.borrow_mut().take()) {
+        Some(message) => fallback.with_geos_message(message),
+        None => fallback,
+    }
+}
+
+unsafe extern "C" fn notice_handler(message: *const c_char, user_data: *mut c_void) {
+    if message.is_null() || user_data.is_null() {
+        return;
+    }
+    let messages = &*(user_data as *const Messages);
+    let message = CStr::from_ptr(message).to_string_lossy().into_owned();
+    // A panic must not unwind through GEOS.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Some(ref handler) = *messages.notice_handler.borrow() {
+            handler(&message);
+        } else if let Ok(notice_handler) = NOTICE_HANDLER.read() {
+            if let Some(ref handler) = *notice_handler {
+                handler(&message);
+            }
+        }
+    }));
+    *messages.last_notice.borrow_mut() = Some(message);
+}
+
+unsafe extern "C" fn error_handler(message: *const c_char, user_data: *mut c_void) {
+    if message.is_null() || user_data.is_null() {
+        return;
+    }
+    let messages = &*(user_data as *const Messages);
+    let message = CStr::from_ptr(message).to_string_lossy().into_owned();
+    *messages.last_error.borrow_mut() = Some(message);
+}
@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by every fallible operation of this crate.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GeosError {
+    /// The WKT or WKB input could not be parsed into a geometry.
+    ParseError(String),
+    /// GEOS raised a topology exception while computing the result.
+    TopologyError(String),
+    /// An argument was rejected, either on the Rust side or by GEOS.
+    InvalidArgument(String),
+    /// A GEOS function returned a NULL pointer.
+    NullReturn(String),
+    /// A GEOS function returned an error code.
+    GenericError(String),
+}
+
+impl GeosError {
+    /// Replaces the description of the error by the message reported by GEOS,
+    /// refining the kind of error when the message names the GEOS exception.
+    pub(crate) fn with_geos_message(self, message: String) -> GeosError {
+        if message.starts_with("ParseException") {
+            return GeosError::ParseError(message);
+        }
+        if message.starts_with("TopologyException") {
+            return GeosError::TopologyError(message);
+        }
+        if message.starts_with("IllegalArgumentException") {
+            return GeosError::InvalidArgument(message);
+        }
+        match self {
+            GeosError::ParseError(_) => GeosError::ParseError(message),
+            GeosError::TopologyError(_) => GeosError::TopologyError(message),
+            GeosError::InvalidArgument(_) => GeosError::InvalidArgument(message),
+            GeosError::NullReturn(_) => GeosError::NullReturn(message),
+            GeosError::GenericError(_) => GeosError::GenericError(message),
+        }
+    }
+}
+
+impl fmt::Display for GeosError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GeosError::ParseError(ref msg) => write!(f, "parse error: {}", msg),
+            GeosError::TopologyError(ref msg) => write!(f, "topology error: {}", msg),
+            GeosError::InvalidArgument(ref msg) => write!(f, "invalid argument: {}", msg),
+            GeosError::NullReturn(ref msg) => write!(f, "NULL returned: {}", msg),
+            GeosError::GenericError(ref msg) => write!(f, "GEOS error: {}", msg),
+        }
+    }
+}
+
+impl Error for GeosError {}
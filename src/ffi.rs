@@ -1,138 +1,630 @@
-use libc::{atexit, c_char, c_double, c_int, c_uint, c_void, size_t};
-use std::sync::{Once, ONCE_INIT};
-use std::ffi::{CStr, CString};
-use std::{ptr, result, str};
+use libc::{c_char, c_double, c_int, c_uint, c_void, size_t};
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::{f64, fmt, ptr, slice, str, vec};
+use geo::Bbox;
+use buffer_params::{BufferParams, CapStyle, JoinStyle};
+use context_handle::{handle, last_error, with_drop_handle};
+use error::GeosError;
+use wkt::{WKTReader, WKTWriter};
 
 #[link(name = "geos_c")]
 extern "C" {
-    fn initGEOS() -> *mut c_void;
+    pub fn GEOS_init_r() -> GEOSContextHandle_t;
     fn GEOSversion() -> *const c_char;
-    fn finishGEOS() -> *mut c_void;
+    pub fn GEOS_finish_r(handle: GEOSContextHandle_t);
+
+    // Message handlers, the non reentrant ones being variadic functions :
+    pub fn GEOSContext_setNoticeMessageHandler_r(
+        handle: GEOSContextHandle_t,
+        nf: GEOSMessageHandler_r,
+        user_data: *mut c_void,
+    ) -> GEOSMessageHandler_r;
+    pub fn GEOSContext_setErrorMessageHandler_r(
+        handle: GEOSContextHandle_t,
+        ef: GEOSMessageHandler_r,
+        user_data: *mut c_void,
+    ) -> GEOSMessageHandler_r;
 
     // API for reading WKT :
-    fn GEOSWKTReader_create() -> *mut GEOSWKTReader;
-    fn GEOSWKTReader_destroy(reader: *mut GEOSWKTReader);
-    fn GEOSWKTReader_read(reader: *mut GEOSWKTReader, wkt: *const c_char) -> *mut c_void;
+    pub fn GEOSWKTReader_create_r(handle: GEOSContextHandle_t) -> *mut GEOSWKTReader;
+    pub fn GEOSWKTReader_destroy_r(handle: GEOSContextHandle_t, reader: *mut GEOSWKTReader);
+    pub fn GEOSWKTReader_read_r(
+        handle: GEOSContextHandle_t,
+        reader: *mut GEOSWKTReader,
+        wkt: *const c_char,
+    ) -> *mut c_void;
 
     // API for writing WKT :
-    fn GEOSWKTWriter_create() -> *mut GEOSWKTWriter;
-    fn GEOSWKTWriter_destroy(writer: *mut GEOSWKTWriter);
-    fn GEOSWKTWriter_write(writer: *mut GEOSWKTWriter, g: *const c_void) -> *const c_char;
-    fn GEOSWKTWriter_setRoundingPrecision(writer: *mut GEOSWKTWriter, precision: c_int);
-
-    fn GEOSFree(buffer: *mut c_void);
-
-    fn GEOSPrepare(g: *const c_void) -> *mut GEOSPreparedGeometry;
-    fn GEOSGeom_destroy(g: *mut c_void);
-    pub fn GEOSGeom_clone(g: *const c_void) -> *mut c_void;
-
-    fn GEOSCoordSeq_create(size: c_uint, dims: c_uint) -> *mut GEOSCoordSequence;
-    fn GEOSCoordSeq_destroy(s: *mut GEOSCoordSequence);
-    fn GEOSCoordSeq_clone(s: *const GEOSCoordSequence) -> *mut GEOSCoordSequence;
-    fn GEOSCoordSeq_setX(s: *mut GEOSCoordSequence, idx: c_uint, val: c_double) -> c_int;
-    fn GEOSCoordSeq_setY(s: *mut GEOSCoordSequence, idx: c_uint, val: c_double) -> c_int;
-    fn GEOSCoordSeq_setZ(s: *mut GEOSCoordSequence, idx: c_uint, val: c_double) -> c_int;
-    fn GEOSCoordSeq_getX(s: *const GEOSCoordSequence, idx: c_uint, val: *mut c_double) -> c_int;
-    fn GEOSCoordSeq_getY(s: *const GEOSCoordSequence, idx: c_uint, val: *mut c_double) -> c_int;
-    fn GEOSCoordSeq_getZ(s: *const GEOSCoordSequence, idx: c_uint, val: *mut c_double) -> c_int;
+    pub fn GEOSWKTWriter_create_r(handle: GEOSContextHandle_t) -> *mut GEOSWKTWriter;
+    pub fn GEOSWKTWriter_destroy_r(handle: GEOSContextHandle_t, writer: *mut GEOSWKTWriter);
+    pub fn GEOSWKTWriter_write_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKTWriter,
+        g: *const c_void,
+    ) -> *mut c_char;
+    pub fn GEOSWKTWriter_setTrim_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKTWriter,
+        trim: c_char,
+    );
+    pub fn GEOSWKTWriter_setRoundingPrecision_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKTWriter,
+        precision: c_int,
+    );
+    pub fn GEOSWKTWriter_setOutputDimension_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKTWriter,
+        dimension: c_int,
+    );
+    pub fn GEOSWKTWriter_getOutputDimension_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKTWriter,
+    ) -> c_int;
+    pub fn GEOSWKTWriter_setOld3D_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKTWriter,
+        use_old_3d: c_int,
+    );
+
+    // API for reading WKB :
+    pub fn GEOSWKBReader_create_r(handle: GEOSContextHandle_t) -> *mut GEOSWKBReader;
+    pub fn GEOSWKBReader_destroy_r(handle: GEOSContextHandle_t, reader: *mut GEOSWKBReader);
+    pub fn GEOSWKBReader_read_r(
+        handle: GEOSContextHandle_t,
+        reader: *mut GEOSWKBReader,
+        wkb: *const u8,
+        size: size_t,
+    ) -> *mut c_void;
+    pub fn GEOSWKBReader_readHEX_r(
+        handle: GEOSContextHandle_t,
+        reader: *mut GEOSWKBReader,
+        hex: *const u8,
+        size: size_t,
+    ) -> *mut c_void;
+
+    // API for writing WKB :
+    pub fn GEOSWKBWriter_create_r(handle: GEOSContextHandle_t) -> *mut GEOSWKBWriter;
+    pub fn GEOSWKBWriter_destroy_r(handle: GEOSContextHandle_t, writer: *mut GEOSWKBWriter);
+    pub fn GEOSWKBWriter_write_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKBWriter,
+        g: *const c_void,
+        size: *mut size_t,
+    ) -> *mut u8;
+    pub fn GEOSWKBWriter_writeHEX_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKBWriter,
+        g: *const c_void,
+        size: *mut size_t,
+    ) -> *mut u8;
+    pub fn GEOSWKBWriter_getOutputDimension_r(
+        handle: GEOSContextHandle_t,
+        writer: *const GEOSWKBWriter,
+    ) -> c_int;
+    pub fn GEOSWKBWriter_setOutputDimension_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKBWriter,
+        dimension: c_int,
+    );
+    pub fn GEOSWKBWriter_getByteOrder_r(
+        handle: GEOSContextHandle_t,
+        writer: *const GEOSWKBWriter,
+    ) -> c_int;
+    pub fn GEOSWKBWriter_setByteOrder_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKBWriter,
+        byte_order: c_int,
+    );
+    pub fn GEOSWKBWriter_getIncludeSRID_r(
+        handle: GEOSContextHandle_t,
+        writer: *const GEOSWKBWriter,
+    ) -> c_char;
+    pub fn GEOSWKBWriter_setIncludeSRID_r(
+        handle: GEOSContextHandle_t,
+        writer: *mut GEOSWKBWriter,
+        include_srid: c_char,
+    );
+
+    pub fn GEOSFree_r(handle: GEOSContextHandle_t, buffer: *mut c_void);
+
+    fn GEOSPrepare_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut GEOSPreparedGeometry;
+    fn GEOSGeom_destroy_r(handle: GEOSContextHandle_t, g: *mut c_void);
+    pub fn GEOSGeom_clone_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+
+    fn GEOSCoordSeq_create_r(
+        handle: GEOSContextHandle_t,
+        size: c_uint,
+        dims: c_uint,
+    ) -> *mut GEOSCoordSequence;
+    fn GEOSCoordSeq_destroy_r(handle: GEOSContextHandle_t, s: *mut GEOSCoordSequence);
+    fn GEOSCoordSeq_clone_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+    ) -> *mut GEOSCoordSequence;
+    fn GEOSCoordSeq_setX_r(
+        handle: GEOSContextHandle_t,
+        s: *mut GEOSCoordSequence,
+        idx: c_uint,
+        val: c_double,
+    ) -> c_int;
+    fn GEOSCoordSeq_setY_r(
+        handle: GEOSContextHandle_t,
+        s: *mut GEOSCoordSequence,
+        idx: c_uint,
+        val: c_double,
+    ) -> c_int;
+    fn GEOSCoordSeq_setZ_r(
+        handle: GEOSContextHandle_t,
+        s: *mut GEOSCoordSequence,
+        idx: c_uint,
+        val: c_double,
+    ) -> c_int;
+    pub fn GEOSCoordSeq_getX_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        idx: c_uint,
+        val: *mut c_double,
+    ) -> c_int;
+    pub fn GEOSCoordSeq_getY_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        idx: c_uint,
+        val: *mut c_double,
+    ) -> c_int;
+    fn GEOSCoordSeq_getZ_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        idx: c_uint,
+        val: *mut c_double,
+    ) -> c_int;
+    #[cfg(geos_v3_8_0)]
+    fn GEOSCoordSeq_setXY_r(
+        handle: GEOSContextHandle_t,
+        s: *mut GEOSCoordSequence,
+        idx: c_uint,
+        x: c_double,
+        y: c_double,
+    ) -> c_int;
+    #[cfg(geos_v3_8_0)]
+    fn GEOSCoordSeq_setXYZ_r(
+        handle: GEOSContextHandle_t,
+        s: *mut GEOSCoordSequence,
+        idx: c_uint,
+        x: c_double,
+        y: c_double,
+        z: c_double,
+    ) -> c_int;
+    #[cfg(geos_v3_8_0)]
+    fn GEOSCoordSeq_getXY_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        idx: c_uint,
+        x: *mut c_double,
+        y: *mut c_double,
+    ) -> c_int;
+    fn GEOSCoordSeq_getSize_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        size: *mut c_uint,
+    ) -> c_int;
+    fn GEOSCoordSeq_getDimensions_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        dims: *mut c_uint,
+    ) -> c_int;
+    #[cfg(geos_v3_10_0)]
+    fn GEOSCoordSeq_copyFromBuffer_r(
+        handle: GEOSContextHandle_t,
+        buf: *const c_double,
+        size: c_uint,
+        has_z: c_int,
+        has_m: c_int,
+    ) -> *mut GEOSCoordSequence;
+    #[cfg(geos_v3_10_0)]
+    fn GEOSCoordSeq_copyToBuffer_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+        buf: *mut c_double,
+        has_z: c_int,
+        has_m: c_int,
+    ) -> c_int;
 
     // Geometry must be a LineString, LinearRing or Point :
-    fn GEOSGeom_getCoordSeq(g: *const c_void) -> *mut GEOSCoordSequence;
+    pub fn GEOSGeom_getCoordSeq_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+    ) -> *mut GEOSCoordSequence;
 
     // Geometry constructor :
-    pub fn GEOSGeom_createPoint(s: *const GEOSCoordSequence) -> *mut c_void;
-    pub fn GEOSGeom_createLineString(s: *const GEOSCoordSequence) -> *mut c_void;
-    pub fn GEOSGeom_createLinearRing(s: *const GEOSCoordSequence) -> *mut c_void;
-    pub fn GEOSGeom_createPolygon(
+    pub fn GEOSGeom_createPoint_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+    ) -> *mut c_void;
+    pub fn GEOSGeom_createLineString_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+    ) -> *mut c_void;
+    pub fn GEOSGeom_createLinearRing_r(
+        handle: GEOSContextHandle_t,
+        s: *const GEOSCoordSequence,
+    ) -> *mut c_void;
+    pub fn GEOSGeom_createPolygon_r(
+        handle: GEOSContextHandle_t,
         shell: *mut c_void,
-        holes: &[*mut c_void],
+        holes: *mut *mut c_void,
         nholes: c_uint,
     ) -> *mut c_void;
-    pub fn GEOSGeom_createCollection(
+    pub fn GEOSGeom_createCollection_r(
+        handle: GEOSContextHandle_t,
         t: c_int,
-        geoms: &[*mut c_void],
+        geoms: *mut *mut c_void,
         ngeoms: c_uint,
     ) -> *mut c_void;
 
+    // Accessors to the components of a GEOSGeometry, owned by the geometry :
+    pub fn GEOSGetNumGeometries_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    pub fn GEOSGetGeometryN_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        n: c_int,
+    ) -> *const c_void;
+    pub fn GEOSGetExteriorRing_r(handle: GEOSContextHandle_t, g: *const c_void) -> *const c_void;
+    pub fn GEOSGetNumInteriorRings_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    pub fn GEOSGetInteriorRingN_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        n: c_int,
+    ) -> *const c_void;
+    // Geometry must be a LineString, the point is a new geometry :
+    fn GEOSGeomGetNumPoints_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSGeomGetPointN_r(handle: GEOSContextHandle_t, g: *const c_void, n: c_int) -> *mut c_void;
+
     // Functions acting on GEOSGeometry :
-    fn GEOSisEmpty(g: *const c_void) -> c_int;
-    fn GEOSisSimple(g: *const c_void) -> c_int;
-    fn GEOSisRing(g: *const c_void) -> c_int;
-    fn GEOSHasZ(g: *const c_void) -> c_int;
-    fn GEOSisClosed(g: *const c_void) -> c_int;
-    fn GEOSisValid(g: *const c_void) -> c_int;
-
-    fn GEOSGeomToWKT(g: *const c_void) -> *const c_char;
-    fn GEOSGeomFromWKB_buf(wkb: *const u8, size: size_t) -> *mut c_void;
-    fn GEOSGeomToWKB_buf(g: *const c_void, size: *mut size_t) -> *const u8;
-    fn GEOSGeomTypeId(g: *const c_void) -> c_int;
-    fn GEOSArea(g: *const c_void, area: *mut c_double) -> c_int;
-    fn GEOSLength(g: *const c_void, distance: *mut c_double) -> c_int;
-    fn GEOSDisjoint(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSTouches(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSIntersects(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSCrosses(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSWithin(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSContains(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSOverlaps(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSEquals(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSEqualsExact(g1: *const c_void, g2: *const c_void, tolerance: c_double) -> c_int;
-    fn GEOSCovers(g1: *const c_void, g2: *const c_void) -> c_int;
-    fn GEOSCoveredBy(g1: *const c_void, g2: *const c_void) -> c_int;
-
-    fn GEOSBuffer(g: *const c_void, width: c_double, quadsegs: c_int) -> *mut c_void;
-    fn GEOSEnvelope(g: *const c_void) -> *mut c_void;
-    fn GEOSConvexHull(g: *const c_void) -> *mut c_void;
-    fn GEOSBoundary(g: *const c_void) -> *mut c_void;
-    fn GEOSGetCentroid(g: *const c_void) -> *mut c_void;
-    fn GEOSSymDifference(g1: *const c_void, g2: *const c_void) -> *mut c_void;
-    fn GEOSDifference(g1: *const c_void, g2: *const c_void) -> *mut c_void;
-    fn GEOSClipByRect(
+    fn GEOSisEmpty_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSisSimple_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSisRing_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSHasZ_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSisClosed_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSisValid_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+
+    fn GEOSGeomToWKT_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_char;
+    fn GEOSGeomFromWKB_buf_r(
+        handle: GEOSContextHandle_t,
+        wkb: *const u8,
+        size: size_t,
+    ) -> *mut c_void;
+    fn GEOSGeomToWKB_buf_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        size: *mut size_t,
+    ) -> *mut u8;
+    pub fn GEOSGeomTypeId_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSGeomType_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_char;
+    fn GEOSGetSRID_r(handle: GEOSContextHandle_t, g: *const c_void) -> c_int;
+    fn GEOSSetSRID_r(handle: GEOSContextHandle_t, g: *mut c_void, srid: c_int);
+    fn GEOSArea_r(handle: GEOSContextHandle_t, g: *const c_void, area: *mut c_double) -> c_int;
+    fn GEOSGeomGetX_r(handle: GEOSContextHandle_t, g: *const c_void, x: *mut c_double) -> c_int;
+    fn GEOSGeomGetY_r(handle: GEOSContextHandle_t, g: *const c_void, y: *mut c_double) -> c_int;
+    #[cfg(geos_v3_7_0)]
+    fn GEOSGeomGetZ_r(handle: GEOSContextHandle_t, g: *const c_void, z: *mut c_double) -> c_int;
+    fn GEOSLength_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        distance: *mut c_double,
+    ) -> c_int;
+    fn GEOSDistance_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        dist: *mut c_double,
+    ) -> c_int;
+    fn GEOSHausdorffDistance_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        dist: *mut c_double,
+    ) -> c_int;
+    fn GEOSHausdorffDistanceDensify_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        densify_frac: c_double,
+        dist: *mut c_double,
+    ) -> c_int;
+    #[cfg(geos_v3_7_0)]
+    fn GEOSFrechetDistance_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        dist: *mut c_double,
+    ) -> c_int;
+    #[cfg(geos_v3_7_0)]
+    fn GEOSFrechetDistanceDensify_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        densify_frac: c_double,
+        dist: *mut c_double,
+    ) -> c_int;
+    #[cfg(geos_v3_10_0)]
+    fn GEOSDistanceWithin_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        dist: c_double,
+    ) -> c_int;
+    fn GEOSNearestPoints_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+    ) -> *mut GEOSCoordSequence;
+    fn GEOSDisjoint_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSTouches_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSIntersects_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void)
+        -> c_int;
+    fn GEOSCrosses_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSWithin_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSContains_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSOverlaps_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSEquals_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSEqualsExact_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        tolerance: c_double,
+    ) -> c_int;
+    fn GEOSCovers_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+    fn GEOSCoveredBy_r(handle: GEOSContextHandle_t, g1: *const c_void, g2: *const c_void) -> c_int;
+
+    fn GEOSBuffer_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        width: c_double,
+        quadsegs: c_int,
+    ) -> *mut c_void;
+    fn GEOSBufferWithStyle_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        width: c_double,
+        quadsegs: c_int,
+        end_cap_style: c_int,
+        join_style: c_int,
+        mitre_limit: c_double,
+    ) -> *mut c_void;
+    fn GEOSBufferWithParams_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        params: *const GEOSBufferParams,
+        width: c_double,
+    ) -> *mut c_void;
+    fn GEOSOffsetCurve_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        width: c_double,
+        quadsegs: c_int,
+        join_style: c_int,
+        mitre_limit: c_double,
+    ) -> *mut c_void;
+    fn GEOSEnvelope_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    fn GEOSConvexHull_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    fn GEOSBoundary_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    fn GEOSGetCentroid_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    fn GEOSPointOnSurface_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    fn GEOSSymDifference_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+    ) -> *mut c_void;
+    fn GEOSIntersection_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+    ) -> *mut c_void;
+    fn GEOSUnion_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+    ) -> *mut c_void;
+    fn GEOSUnaryUnion_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    fn GEOSUnionCascaded_r(handle: GEOSContextHandle_t, g: *const c_void) -> *mut c_void;
+    #[cfg(geos_v3_9_0)]
+    fn GEOSIntersectionPrec_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        grid_size: c_double,
+    ) -> *mut c_void;
+    #[cfg(geos_v3_9_0)]
+    fn GEOSUnionPrec_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        grid_size: c_double,
+    ) -> *mut c_void;
+    #[cfg(geos_v3_9_0)]
+    fn GEOSUnaryUnionPrec_r(
+        handle: GEOSContextHandle_t,
+        g: *const c_void,
+        grid_size: c_double,
+    ) -> *mut c_void;
+    #[cfg(geos_v3_9_0)]
+    fn GEOSDifferencePrec_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        grid_size: c_double,
+    ) -> *mut c_void;
+    #[cfg(geos_v3_9_0)]
+    fn GEOSSymDifferencePrec_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        grid_size: c_double,
+    ) -> *mut c_void;
+    fn GEOSDifference_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+    ) -> *mut c_void;
+    fn GEOSClipByRect_r(
+        handle: GEOSContextHandle_t,
         g: *const c_void,
         xmin: c_double,
         ymin: c_double,
         xmax: c_double,
         ymax: c_double,
     ) -> *mut c_void;
-    fn GEOSSnap(g1: *const c_void, g2: *const c_void, tolerance: c_double) -> *mut c_void;
-    fn GEOSGeom_extractUniquePoints(g: *const c_void) -> *mut c_void;
+    fn GEOSSnap_r(
+        handle: GEOSContextHandle_t,
+        g1: *const c_void,
+        g2: *const c_void,
+        tolerance: c_double,
+    ) -> *mut c_void;
+    fn GEOSGeom_extractUniquePoints_r(handle: GEOSContextHandle_t, g: *const c_void)
+        -> *mut c_void;
 
     // Functions acting on GEOSPreparedGeometry :
-    fn GEOSPreparedContains(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedContainsProperly(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedCoveredBy(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedCovers(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedCrosses(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedDisjoint(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedIntersects(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedOverlaps(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedTouches(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedWithin(pg1: *const GEOSPreparedGeometry, g2: *const c_void) -> c_int;
-    fn GEOSPreparedGeom_destroy(g: *mut GEOSPreparedGeometry);
+    fn GEOSPreparedContains_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedContainsProperly_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedCoveredBy_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedCovers_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedCrosses_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedDisjoint_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedIntersects_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedOverlaps_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedTouches_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    fn GEOSPreparedWithin_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> c_int;
+    #[cfg(geos_v3_9_0)]
+    fn GEOSPreparedNearestPoints_r(
+        handle: GEOSContextHandle_t,
+        pg1: *const GEOSPreparedGeometry,
+        g2: *const c_void,
+    ) -> *mut GEOSCoordSequence;
+    fn GEOSPreparedGeom_destroy_r(handle: GEOSContextHandle_t, g: *mut GEOSPreparedGeometry);
+
+    // API for the buffer parameters :
+    pub fn GEOSBufferParams_create_r(handle: GEOSContextHandle_t) -> *mut GEOSBufferParams;
+    pub fn GEOSBufferParams_destroy_r(handle: GEOSContextHandle_t, params: *mut GEOSBufferParams);
+    pub fn GEOSBufferParams_setEndCapStyle_r(
+        handle: GEOSContextHandle_t,
+        params: *mut GEOSBufferParams,
+        style: c_int,
+    ) -> c_int;
+    pub fn GEOSBufferParams_setJoinStyle_r(
+        handle: GEOSContextHandle_t,
+        params: *mut GEOSBufferParams,
+        style: c_int,
+    ) -> c_int;
+    pub fn GEOSBufferParams_setMitreLimit_r(
+        handle: GEOSContextHandle_t,
+        params: *mut GEOSBufferParams,
+        mitre_limit: c_double,
+    ) -> c_int;
+    pub fn GEOSBufferParams_setQuadrantSegments_r(
+        handle: GEOSContextHandle_t,
+        params: *mut GEOSBufferParams,
+        quadsegs: c_int,
+    ) -> c_int;
+    pub fn GEOSBufferParams_setSingleSided_r(
+        handle: GEOSContextHandle_t,
+        params: *mut GEOSBufferParams,
+        single_sided: c_int,
+    ) -> c_int;
 }
 
+#[allow(non_camel_case_types)]
+pub enum GEOSContextHandle_HS {}
+#[allow(non_camel_case_types)]
+pub type GEOSContextHandle_t = *mut GEOSContextHandle_HS;
+#[allow(non_camel_case_types)]
+pub type GEOSMessageHandler_r = Option<unsafe extern "C" fn(*const c_char, *mut c_void)>;
+
 pub enum GEOSWKTReader {}
 pub enum GEOSWKTWriter {}
+pub enum GEOSWKBReader {}
+pub enum GEOSWKBWriter {}
+pub enum GEOSBufferParams {}
 pub enum GEOSPreparedGeometry {}
 pub enum GEOSCoordSequence {}
 
-#[derive(Debug)]
+/// Type of a geometry, as given by `GEOSGeomTypeId`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(C)]
-#[allow(dead_code)]
-pub enum GEOSGeomTypes {
-    GEOS_POINT = 0,
-    GEOS_LINESTRING,
-    GEOS_LINEARRING,
-    GEOS_POLYGON,
-    GEOS_MULTIPOINT,
-    GEOS_MULTILINESTRING,
-    GEOS_MULTIPOLYGON,
-    GEOS_GEOMETRYCOLLECTION,
+pub enum GeometryType {
+    Point = 0,
+    LineString,
+    LinearRing,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub struct GeosError {
-    pub desc: &'static str,
+impl GeometryType {
+    pub(crate) fn from_c_int(type_id: c_int) -> Result<GeometryType, GeosError> {
+        match type_id {
+            0 => Ok(GeometryType::Point),
+            1 => Ok(GeometryType::LineString),
+            2 => Ok(GeometryType::LinearRing),
+            3 => Ok(GeometryType::Polygon),
+            4 => Ok(GeometryType::MultiPoint),
+            5 => Ok(GeometryType::MultiLineString),
+            6 => Ok(GeometryType::MultiPolygon),
+            7 => Ok(GeometryType::GeometryCollection),
+            -1 => Err(last_error(GeosError::GenericError(
+                "GEOSGeomTypeId failed".to_string(),
+            ))),
+            _ => Err(GeosError::GenericError(format!(
+                "Unknown geometry type id: {}",
+                type_id
+            ))),
+        }
+    }
 }
 
 pub fn _string(raw_ptr: *const c_char) -> String {
@@ -140,38 +632,110 @@ pub fn _string(raw_ptr: *const c_char) -> String {
     return str::from_utf8(c_str.to_bytes()).unwrap().to_string();
 }
 
-pub fn _point(s: &CoordSeq) -> GGeom {
-    GGeom::new_from_c_obj(unsafe {
-        GEOSGeom_createPoint(GEOSCoordSeq_clone(s.0 as *const GEOSCoordSequence))
-    })
+fn check_predicate(ret_val: c_int, func: &str) -> Result<bool, GeosError> {
+    match ret_val {
+        1 => Ok(true),
+        0 => Ok(false),
+        _ => Err(last_error(GeosError::GenericError(format!(
+            "{} failed",
+            func
+        )))),
+    }
 }
 
-pub fn _lineString(s: &CoordSeq) -> GGeom {
-    GGeom::new_from_c_obj(unsafe {
-        GEOSGeom_createLineString(GEOSCoordSeq_clone(s.0 as *const GEOSCoordSequence))
-    })
+fn check_double(ret_val: c_int, value: f64, func: &str) -> Result<f64, GeosError> {
+    if ret_val == 0 {
+        return Err(last_error(GeosError::GenericError(format!(
+            "{} failed",
+            func
+        ))));
+    }
+    Ok(value)
+}
+
+fn check_coord_seq(s: *mut GEOSCoordSequence, func: &str) -> Result<CoordSeq, GeosError> {
+    if s.is_null() {
+        return Err(last_error(GeosError::NullReturn(format!(
+            "{} returned NULL",
+            func
+        ))));
+    }
+    Ok(CoordSeq::new_from_c_obj(s))
+}
+
+fn check_setter(ret_val: c_int, func: &str) -> Result<(), GeosError> {
+    if ret_val == 0 {
+        return Err(last_error(GeosError::GenericError(format!(
+            "{} failed",
+            func
+        ))));
+    }
+    Ok(())
+}
+
+fn check_count(ret_val: c_int, func: &str) -> Result<usize, GeosError> {
+    if ret_val < 0 {
+        return Err(last_error(GeosError::GenericError(format!(
+            "{} failed",
+            func
+        ))));
+    }
+    Ok(ret_val as usize)
+}
+
+fn coord_seq_size(s: *const GEOSCoordSequence) -> Result<c_uint, GeosError> {
+    let mut size: c_uint = 0;
+    if unsafe { GEOSCoordSeq_getSize_r(handle(), s, &mut size) } == 0 {
+        return Err(last_error(GeosError::GenericError(
+            "GEOSCoordSeq_getSize failed".to_string(),
+        )));
+    }
+    Ok(size)
+}
+
+fn check_index(n: usize, len: usize) -> Result<c_int, GeosError> {
+    if n >= len {
+        return Err(GeosError::InvalidArgument(format!(
+            "Index {} out of range, the geometry has {} elements",
+            n, len
+        )));
+    }
+    Ok(n as c_int)
+}
+
+fn check_overlay(g: *mut c_void, func: &str) -> Result<GGeom, GeosError> {
+    if g.is_null() {
+        return Err(last_error(GeosError::TopologyError(format!(
+            "{} failed",
+            func
+        ))));
+    }
+    GGeom::new_from_c_obj(g)
 }
 
-pub fn _linearRing(s: &CoordSeq) -> GGeom {
+pub fn _point(s: &CoordSeq) -> Result<GGeom, GeosError> {
     GGeom::new_from_c_obj(unsafe {
-        GEOSGeom_createLinearRing(GEOSCoordSeq_clone(s.0 as *const GEOSCoordSequence))
+        GEOSGeom_createPoint_r(
+            handle(),
+            GEOSCoordSeq_clone_r(handle(), s.0 as *const GEOSCoordSequence),
+        )
     })
 }
 
-#[allow(dead_code)]
-pub fn snap(g1: &GGeom, g2: &GGeom, tolerance: f64) -> GGeom {
-    GGeom::new_from_c_obj(unsafe { GEOSSnap(g1.c_obj, g2.c_obj, tolerance as c_double) })
+pub fn _lineString(s: &CoordSeq) -> Result<GGeom, GeosError> {
+    GGeom::new_from_c_obj(unsafe {
+        GEOSGeom_createLineString_r(
+            handle(),
+            GEOSCoordSeq_clone_r(handle(), s.0 as *const GEOSCoordSequence),
+        )
+    })
 }
 
-#[allow(dead_code)]
-pub fn clip_by_rect(g: &GGeom, xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> GGeom {
+pub fn _linearRing(s: &CoordSeq) -> Result<GGeom, GeosError> {
     GGeom::new_from_c_obj(unsafe {
-        GEOSClipByRect(
-            g.c_obj,
-            xmin as c_double,
-            ymin as c_double,
-            xmax as c_double,
-            ymax as c_double,
+        GEOSGeom_createLinearRing_r(
+            handle(),
+            GEOSCoordSeq_clone_r(handle(), s.0 as *const GEOSCoordSequence),
         )
     })
 }
@@ -180,304 +744,810 @@ pub fn version() -> String {
     unsafe { _string(GEOSversion()) }
 }
 
-fn initialize() {
-    static INIT: Once = ONCE_INIT;
-    INIT.call_once(|| unsafe {
-        initGEOS();
-        assert_eq!(atexit(cleanup), 0);
-    });
-
-    extern "C" fn cleanup() {
-        unsafe {
-            finishGEOS();
-        }
-    }
-}
-
 pub struct CoordSeq(*mut GEOSCoordSequence);
 
+// A GEOS object isn't tied to the context it was created with, so it can be
+// used from any thread, but not from several threads at once.
+unsafe impl Send for CoordSeq {}
+
 impl Drop for CoordSeq {
     fn drop(&mut self) {
-        unsafe { GEOSCoordSeq_destroy(self.0 as *mut GEOSCoordSequence) };
+        with_drop_handle(|handle| unsafe {
+            GEOSCoordSeq_destroy_r(handle, self.0 as *mut GEOSCoordSequence)
+        });
         self.0 = ptr::null_mut();
     }
 }
 
+/// Panics if GEOS fails to copy the sequence, see `CoordSeq::try_clone`.
 impl Clone for CoordSeq {
     fn clone(&self) -> CoordSeq {
-        CoordSeq(unsafe { GEOSCoordSeq_clone(self.0 as *const GEOSCoordSequence) })
+        self.try_clone().expect("GEOSCoordSeq_clone failed")
     }
 }
 
 impl CoordSeq {
-    pub fn new(size: u32, dims: u32) -> CoordSeq {
-        initialize();
-        CoordSeq(unsafe { GEOSCoordSeq_create(size as c_uint, dims as c_uint) })
+    pub fn new(size: u32, dims: u32) -> Result<CoordSeq, GeosError> {
+        let c_obj = unsafe { GEOSCoordSeq_create_r(handle(), size as c_uint, dims as c_uint) };
+        if c_obj.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSCoordSeq_create returned NULL".to_string(),
+            )));
+        }
+        Ok(CoordSeq(c_obj))
     }
 
-    pub fn new_from_c_obj(c_obj: *mut GEOSCoordSequence) -> CoordSeq {
+    pub(crate) fn new_from_c_obj(c_obj: *mut GEOSCoordSequence) -> CoordSeq {
         CoordSeq(c_obj)
     }
-    pub fn set_x(&self, idx: u32, val: f64) -> i32 {
-        let ret_val = unsafe {
-            GEOSCoordSeq_setX(
-                self.0 as *mut GEOSCoordSequence,
-                idx as c_uint,
-                val as c_double,
-            )
-        };
-        return ret_val;
+
+    /// Copies the sequence, returning an error instead of panicking like `clone()`.
+    pub fn try_clone(&self) -> Result<CoordSeq, GeosError> {
+        check_coord_seq(
+            unsafe { GEOSCoordSeq_clone_r(handle(), self.0 as *const GEOSCoordSequence) },
+            "GEOSCoordSeq_clone",
+        )
     }
-    pub fn set_y(&self, idx: u32, val: f64) -> i32 {
-        let ret_val = unsafe {
-            GEOSCoordSeq_setY(
-                self.0 as *mut GEOSCoordSequence,
-                idx as c_uint,
-                val as c_double,
-            )
-        };
-        return ret_val;
+
+    /// Creates a 2D sequence from `[x, y]` coordinates.
+    pub fn from_slice(coords: &[[f64; 2]]) -> Result<CoordSeq, GeosError> {
+        // An array of arrays is laid out as a contiguous array of f64.
+        let buf = unsafe { slice::from_raw_parts(coords.as_ptr() as *const f64, coords.len() * 2) };
+        CoordSeq::from_buffer(buf, 2)
     }
-    pub fn set_z(&self, idx: u32, val: f64) -> i32 {
-        let ret_val = unsafe {
-            GEOSCoordSeq_setZ(
-                self.0 as *mut GEOSCoordSequence,
-                idx as c_uint,
-                val as c_double,
-            )
-        };
-        return ret_val;
+
+    /// Creates a 3D sequence from `[x, y, z]` coordinates.
+    pub fn from_slice_3d(coords: &[[f64; 3]]) -> Result<CoordSeq, GeosError> {
+        let buf = unsafe { slice::from_raw_parts(coords.as_ptr() as *const f64, coords.len() * 3) };
+        CoordSeq::from_buffer(buf, 3)
     }
 
-    pub fn get_x(&self, idx: u32) -> f64 {
-        let n_mut_ref = &mut 0.0;
-        let ret_val = unsafe {
-            GEOSCoordSeq_getX(
-                self.0 as *const GEOSCoordSequence,
-                idx as c_uint,
-                n_mut_ref as *mut c_double,
+    #[cfg(geos_v3_10_0)]
+    fn from_buffer(buf: &[f64], dims: usize) -> Result<CoordSeq, GeosError> {
+        let size = buf.len() / dims;
+        let c_obj = unsafe {
+            GEOSCoordSeq_copyFromBuffer_r(
+                handle(),
+                buf.as_ptr(),
+                size as c_uint,
+                (dims == 3) as c_int,
+                0,
             )
         };
-        if ret_val == 0 {
-            panic!("Error when getting coordinates from CoordSeq");
+        if c_obj.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSCoordSeq_copyFromBuffer returned NULL".to_string(),
+            )));
         }
-        return *n_mut_ref;
+        Ok(CoordSeq(c_obj))
     }
 
-    pub fn get_y(&self, idx: u32) -> f64 {
-        let n_mut_ref = &mut 0.0;
-        let ret_val = unsafe {
-            GEOSCoordSeq_getY(
-                self.0 as *const GEOSCoordSequence,
-                idx as c_uint,
-                n_mut_ref as *mut c_double,
-            )
-        };
-        if ret_val == 0 {
-            panic!("Error when getting coordinates from CoordSeq");
+    #[cfg(not(geos_v3_10_0))]
+    fn from_buffer(buf: &[f64], dims: usize) -> Result<CoordSeq, GeosError> {
+        let seq = CoordSeq::new((buf.len() / dims) as u32, dims as u32)?;
+        // Fetched once, as each call to handle() clears the last error message.
+        let handle = handle();
+        for (idx, coord) in buf.chunks(dims).enumerate() {
+            let idx = idx as c_uint;
+            if unsafe { GEOSCoordSeq_setX_r(handle, seq.0, idx, coord[0]) } == 0
+                || unsafe { GEOSCoordSeq_setY_r(handle, seq.0, idx, coord[1]) } == 0
+                || (dims == 3 && unsafe { GEOSCoordSeq_setZ_r(handle, seq.0, idx, coord[2]) } == 0)
+            {
+                return Err(last_error(GeosError::GenericError(
+                    "Unable to set the coordinates of the CoordSeq".to_string(),
+                )));
+            }
         }
-        return *n_mut_ref;
+        Ok(seq)
+    }
+
+    /// Returns the `[x, y]` coordinates of the sequence.
+    pub fn to_vec(&self) -> Result<Vec<[f64; 2]>, GeosError> {
+        let buf = self.to_buffer(2)?;
+        Ok(buf.chunks(2).map(|c| [c[0], c[1]]).collect())
     }
 
-    pub fn get_z(&self, idx: u32) -> f64 {
-        let n_mut_ref = &mut 0.0;
+    /// Returns the `[x, y, z]` coordinates of the sequence, Z being NaN for 2D coordinates.
+    pub fn to_vec_3d(&self) -> Result<Vec<[f64; 3]>, GeosError> {
+        let buf = self.to_buffer(3)?;
+        Ok(buf.chunks(3).map(|c| [c[0], c[1], c[2]]).collect())
+    }
+
+    #[cfg(geos_v3_10_0)]
+    fn to_buffer(&self, dims: usize) -> Result<Vec<f64>, GeosError> {
+        let mut buf = vec![0.; self.size()? * dims];
         let ret_val = unsafe {
-            GEOSCoordSeq_getZ(
-                self.0 as *const GEOSCoordSequence,
-                idx as c_uint,
-                n_mut_ref as *mut c_double,
-            )
+            GEOSCoordSeq_copyToBuffer_r(handle(), self.0, buf.as_mut_ptr(), (dims == 3) as c_int, 0)
         };
         if ret_val == 0 {
-            panic!("Error when getting coordinates from CoordSeq");
+            return Err(last_error(GeosError::GenericError(
+                "GEOSCoordSeq_copyToBuffer failed".to_string(),
+            )));
+        }
+        Ok(buf)
+    }
+
+    #[cfg(not(geos_v3_10_0))]
+    fn to_buffer(&self, dims: usize) -> Result<Vec<f64>, GeosError> {
+        let mut buf = Vec::with_capacity(self.size()? * dims);
+        for coord in self.iter()? {
+            let (x, y, z) = coord?;
+            buf.push(x);
+            buf.push(y);
+            if dims == 3 {
+                buf.push(z.unwrap_or(f64::NAN));
+            }
         }
-        return *n_mut_ref;
+        Ok(buf)
+    }
+
+    /// Returns the number of coordinates of the sequence.
+    pub fn size(&self) -> Result<usize, GeosError> {
+        Ok(CoordSeqInfo::new(self.0)?.size as usize)
+    }
+
+    /// Returns the number of dimensions of the coordinates, 2 or 3.
+    pub fn dims(&self) -> Result<usize, GeosError> {
+        Ok(CoordSeqInfo::new(self.0)?.dims as usize)
+    }
+
+    /// Iterates over the `(x, y, z)` coordinates of the sequence.
+    pub fn iter(&self) -> Result<Coords<'_>, GeosError> {
+        Ok(Coords {
+            seqs: vec![CoordSeqInfo::new(self.0)?],
+            seq_index: 0,
+            coord_index: 0,
+            geom: PhantomData,
+        })
+    }
+
+    fn check_coord_index(&self, idx: u32) -> Result<c_uint, GeosError> {
+        let size = coord_seq_size(self.0)?;
+        if idx >= size {
+            return Err(GeosError::InvalidArgument(format!(
+                "Index {} out of range, the CoordSeq has {} coordinates",
+                idx, size
+            )));
+        }
+        Ok(idx as c_uint)
+    }
+
+    pub fn set_x(&mut self, idx: u32, val: f64) -> Result<(), GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let ret_val = unsafe { GEOSCoordSeq_setX_r(handle(), self.0, idx, val as c_double) };
+        check_setter(ret_val, "GEOSCoordSeq_setX")
+    }
+
+    pub fn set_y(&mut self, idx: u32, val: f64) -> Result<(), GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let ret_val = unsafe { GEOSCoordSeq_setY_r(handle(), self.0, idx, val as c_double) };
+        check_setter(ret_val, "GEOSCoordSeq_setY")
+    }
+
+    pub fn set_z(&mut self, idx: u32, val: f64) -> Result<(), GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let ret_val = unsafe { GEOSCoordSeq_setZ_r(handle(), self.0, idx, val as c_double) };
+        check_setter(ret_val, "GEOSCoordSeq_setZ")
+    }
+
+    #[cfg(geos_v3_8_0)]
+    pub fn set_xy(&mut self, idx: u32, x: f64, y: f64) -> Result<(), GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let ret_val = unsafe { GEOSCoordSeq_setXY_r(handle(), self.0, idx, x, y) };
+        check_setter(ret_val, "GEOSCoordSeq_setXY")
+    }
+
+    #[cfg(not(geos_v3_8_0))]
+    pub fn set_xy(&mut self, idx: u32, x: f64, y: f64) -> Result<(), GeosError> {
+        self.set_x(idx, x)?;
+        self.set_y(idx, y)
+    }
+
+    #[cfg(geos_v3_8_0)]
+    pub fn set_xyz(&mut self, idx: u32, x: f64, y: f64, z: f64) -> Result<(), GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let ret_val = unsafe { GEOSCoordSeq_setXYZ_r(handle(), self.0, idx, x, y, z) };
+        check_setter(ret_val, "GEOSCoordSeq_setXYZ")
+    }
+
+    #[cfg(not(geos_v3_8_0))]
+    pub fn set_xyz(&mut self, idx: u32, x: f64, y: f64, z: f64) -> Result<(), GeosError> {
+        self.set_xy(idx, x, y)?;
+        self.set_z(idx, z)
+    }
+
+    pub fn get_x(&self, idx: u32) -> Result<f64, GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let mut x = 0.;
+        let ret_val = unsafe { GEOSCoordSeq_getX_r(handle(), self.0, idx, &mut x) };
+        check_double(ret_val, x, "GEOSCoordSeq_getX")
+    }
+
+    pub fn get_y(&self, idx: u32) -> Result<f64, GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let mut y = 0.;
+        let ret_val = unsafe { GEOSCoordSeq_getY_r(handle(), self.0, idx, &mut y) };
+        check_double(ret_val, y, "GEOSCoordSeq_getY")
+    }
+
+    pub fn get_z(&self, idx: u32) -> Result<f64, GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let mut z = 0.;
+        let ret_val = unsafe { GEOSCoordSeq_getZ_r(handle(), self.0, idx, &mut z) };
+        check_double(ret_val, z, "GEOSCoordSeq_getZ")
+    }
+
+    #[cfg(geos_v3_8_0)]
+    pub fn get_xy(&self, idx: u32) -> Result<(f64, f64), GeosError> {
+        let idx = self.check_coord_index(idx)?;
+        let (mut x, mut y) = (0., 0.);
+        let ret_val = unsafe { GEOSCoordSeq_getXY_r(handle(), self.0, idx, &mut x, &mut y) };
+        let x = check_double(ret_val, x, "GEOSCoordSeq_getXY")?;
+        Ok((x, y))
+    }
+
+    #[cfg(not(geos_v3_8_0))]
+    pub fn get_xy(&self, idx: u32) -> Result<(f64, f64), GeosError> {
+        Ok((self.get_x(idx)?, self.get_y(idx)?))
     }
 }
 
 pub struct GGeom {
-    pub c_obj: *mut c_void,
-    pub area: f64,
-    pub _type: i32,
+    pub(crate) c_obj: *mut c_void,
 }
 
+unsafe impl Send for GGeom {}
+
 impl Drop for GGeom {
     fn drop(&mut self) {
-        unsafe { GEOSGeom_destroy(self.c_obj as *mut c_void) };
+        with_drop_handle(|handle| unsafe { GEOSGeom_destroy_r(handle, self.c_obj as *mut c_void) });
         self.c_obj = ptr::null_mut();
     }
 }
 
+/// Panics if GEOS fails to copy the geometry, see `GGeom::try_clone`.
 impl Clone for GGeom {
     fn clone(&self) -> GGeom {
-        let n_obj = unsafe { GEOSGeom_clone(self.c_obj as *const c_void) };
-        GGeom {
-            c_obj: n_obj,
-            area: self.area,
-            _type: self._type,
-        }
+        self.try_clone().expect("GEOSGeom_clone failed")
     }
 }
 
 impl GGeom {
-    pub fn new(wkt: &str) -> GGeom {
-        initialize();
-        let c_str = CString::new(wkt).unwrap();
-        let reader = unsafe { GEOSWKTReader_create() };
-        let obj = unsafe { GEOSWKTReader_read(reader, c_str.as_ptr()) };
+    pub fn new(wkt: &str) -> Result<GGeom, GeosError> {
+        WKTReader::new()?.read(wkt)
+    }
+
+    pub fn from_wkb(wkb: &[u8]) -> Result<GGeom, GeosError> {
+        unsafe { GGeom::new_from_wkb(wkb.as_ptr(), wkb.len() as size_t) }
+    }
+
+    /// Reads a geometry from a raw WKB buffer, prefer `GGeom::from_wkb`.
+    ///
+    /// # Safety
+    ///
+    /// `wkb` must point to at least `size` readable bytes.
+    pub unsafe fn new_from_wkb(wkb: *const u8, size: size_t) -> Result<GGeom, GeosError> {
+        let obj = GEOSGeomFromWKB_buf_r(handle(), wkb, size);
         if obj.is_null() {
-            panic!("Invalid geometry");
+            return Err(last_error(GeosError::ParseError("invalid WKB".to_string())));
         }
-        unsafe { GEOSWKTReader_destroy(reader) };
         GGeom::new_from_c_obj(obj)
     }
 
-    pub fn new_from_wkb(wkb: *const u8, size: size_t) -> GGeom {
-        initialize();
-        // let strr = CString::new(wkb).unwrap();
-        // let t = strr.as_bytes();
-        let obj = unsafe { GEOSGeomFromWKB_buf(wkb as *const u8, size as size_t) };
-        GGeom::new_from_c_obj(obj)
+    pub(crate) fn new_from_c_obj(g: *mut c_void) -> Result<GGeom, GeosError> {
+        if g.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOS returned a NULL geometry".to_string(),
+            )));
+        }
+        Ok(GGeom { c_obj: g })
     }
 
-    pub fn new_from_c_obj(g: *mut c_void) -> GGeom {
-        if g.is_null() {
-            panic!("Invalid geometry");
+    /// Copies the geometry, returning an error instead of panicking like `clone()`.
+    pub fn try_clone(&self) -> Result<GGeom, GeosError> {
+        let c_obj = unsafe { GEOSGeom_clone_r(handle(), self.c_obj as *const c_void) };
+        if c_obj.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSGeom_clone returned NULL".to_string(),
+            )));
+        }
+        Ok(GGeom { c_obj })
+    }
+
+    pub fn is_valid(&self) -> Result<bool, GeosError> {
+        let ret_val = unsafe { GEOSisValid_r(handle(), self.c_obj as *const c_void) };
+        check_predicate(ret_val, "GEOSisValid")
+    }
+
+    /// Returns the number of components of a collection, 1 for the other geometries.
+    pub fn num_geometries(&self) -> Result<usize, GeosError> {
+        let ret_val = unsafe { GEOSGetNumGeometries_r(handle(), self.c_obj as *const c_void) };
+        check_count(ret_val, "GEOSGetNumGeometries")
+    }
+
+    /// Returns the component `n` of a collection, or the geometry itself for `n` = 0
+    /// if it isn't a collection.
+    pub fn get_geometry_n(&self, n: usize) -> Result<GGeomRef<'_>, GeosError> {
+        let n = check_index(n, self.num_geometries()?)?;
+        let g = unsafe { GEOSGetGeometryN_r(handle(), self.c_obj as *const c_void, n) };
+        GGeomRef::new(g, "GEOSGetGeometryN")
+    }
+
+    /// Returns the exterior ring of a Polygon.
+    pub fn exterior_ring(&self) -> Result<GGeomRef<'_>, GeosError> {
+        let g = unsafe { GEOSGetExteriorRing_r(handle(), self.c_obj as *const c_void) };
+        GGeomRef::new(g, "GEOSGetExteriorRing")
+    }
+
+    /// Returns the number of interior rings of a Polygon.
+    pub fn num_interior_rings(&self) -> Result<usize, GeosError> {
+        let ret_val = unsafe { GEOSGetNumInteriorRings_r(handle(), self.c_obj as *const c_void) };
+        check_count(ret_val, "GEOSGetNumInteriorRings")
+    }
+
+    pub fn get_interior_ring_n(&self, n: usize) -> Result<GGeomRef<'_>, GeosError> {
+        let n = check_index(n, self.num_interior_rings()?)?;
+        let g = unsafe { GEOSGetInteriorRingN_r(handle(), self.c_obj as *const c_void, n) };
+        GGeomRef::new(g, "GEOSGetInteriorRingN")
+    }
+
+    /// Returns all the interior rings of a Polygon.
+    pub fn interior_rings(&self) -> Result<Vec<GGeomRef<'_>>, GeosError> {
+        (0..self.num_interior_rings()?)
+            .map(|n| self.get_interior_ring_n(n))
+            .collect()
+    }
+
+    /// Returns the number of points of a LineString or LinearRing.
+    pub fn num_points(&self) -> Result<usize, GeosError> {
+        let ret_val = unsafe { GEOSGeomGetNumPoints_r(handle(), self.c_obj as *const c_void) };
+        check_count(ret_val, "GEOSGeomGetNumPoints")
+    }
+
+    /// Returns the point `n` of a LineString or LinearRing, as a new Point.
+    pub fn get_point_n(&self, n: usize) -> Result<GGeom, GeosError> {
+        let n = check_index(n, self.num_points()?)?;
+        GGeom::new_from_c_obj(unsafe {
+            GEOSGeomGetPointN_r(handle(), self.c_obj as *const c_void, n)
+        })
+    }
+
+    /// Iterates over the components of a collection, or over the geometry
+    /// itself if it isn't a collection.
+    pub fn parts(&self) -> Result<Parts<'_>, GeosError> {
+        let parts = (0..self.num_geometries()?)
+            .map(|n| self.get_geometry_n(n))
+            .collect::<Result<Vec<_>, GeosError>>()?;
+        Ok(Parts(parts.into_iter()))
+    }
+
+    /// Iterates over the rings of a Polygon, the exterior ring first.
+    pub fn rings(&self) -> Result<Rings<'_>, GeosError> {
+        if self.geometry_type()? != GeometryType::Polygon {
+            return Err(GeosError::InvalidArgument(
+                "Geometry must be a Polygon".to_string(),
+            ));
         }
-        let area = GGeom::_area(g as *const c_void);
-        let type_geom = unsafe { GEOSGeomTypeId(g as *const c_void) as i32 };
-        GGeom {
-            c_obj: g,
-            area: area,
-            _type: type_geom,
+        let mut rings = vec![self.exterior_ring()?];
+        rings.extend(self.interior_rings()?);
+        Ok(Rings(rings.into_iter()))
+    }
+
+    /// Iterates over the `(x, y, z)` coordinates of all the points of the
+    /// geometry, reading them from the geometry without copying it.
+    pub fn coords(&self) -> Result<Coords<'_>, GeosError> {
+        let mut seqs = Vec::new();
+        self.collect_coord_seqs(&mut seqs)?;
+        Ok(Coords {
+            seqs,
+            seq_index: 0,
+            coord_index: 0,
+            geom: PhantomData,
+        })
+    }
+
+    fn collect_coord_seqs(&self, seqs: &mut Vec<CoordSeqInfo>) -> Result<(), GeosError> {
+        match self.geometry_type()? {
+            GeometryType::Point | GeometryType::LineString | GeometryType::LinearRing => {
+                let s = unsafe { GEOSGeom_getCoordSeq_r(handle(), self.c_obj as *const c_void) };
+                if s.is_null() {
+                    return Err(last_error(GeosError::NullReturn(
+                        "GEOSGeom_getCoordSeq returned NULL".to_string(),
+                    )));
+                }
+                seqs.push(CoordSeqInfo::new(s)?);
+            }
+            GeometryType::Polygon => {
+                for ring in self.rings()? {
+                    ring.collect_coord_seqs(seqs)?;
+                }
+            }
+            _ => {
+                for part in self.parts()? {
+                    part.collect_coord_seqs(seqs)?;
+                }
+            }
         }
+        Ok(())
     }
 
-    pub fn is_valid(&self) -> bool {
-        let rv = unsafe { GEOSisValid(self.c_obj as *const c_void) };
-        return if rv == 1 { true } else { false };
+    /// Prepares the geometry for faster predicates, see `PreparedGGeom`.
+    pub fn to_prepared(&self) -> Result<PreparedGGeom<'_>, GeosError> {
+        PreparedGGeom::new(self)
     }
 
-    pub fn get_coord_seq(&self) -> Result<CoordSeq, &'static str> {
-        match self._type {
-            0 | 1 | 2 => {
-                let t = unsafe { GEOSGeom_getCoordSeq(self.c_obj as *const c_void) };
+    /// Returns the coordinates of a Point, LineString or LinearRing, borrowed
+    /// from the geometry.
+    pub fn get_coord_seq(&self) -> Result<CoordSeqRef<'_>, GeosError> {
+        match self.geometry_type()? {
+            GeometryType::Point | GeometryType::LineString | GeometryType::LinearRing => {
+                let t = unsafe { GEOSGeom_getCoordSeq_r(handle(), self.c_obj as *const c_void) };
                 if t.is_null() {
-                    return Err("Error retrieving Coordinates Sequence");
+                    return Err(last_error(GeosError::NullReturn(
+                        "GEOSGeom_getCoordSeq returned NULL".to_string(),
+                    )));
                 }
-                Ok(CoordSeq::new_from_c_obj(t as *mut GEOSCoordSequence))
+                Ok(CoordSeqRef {
+                    // The CoordSeq is never dropped, so the geometry stays the only owner.
+                    seq: ManuallyDrop::new(CoordSeq(t as *mut GEOSCoordSequence)),
+                    geom: PhantomData,
+                })
             }
-            _ => Err("Not implemented - Geometry must be a Point, LineString or LinearRing"),
+            _ => Err(GeosError::InvalidArgument(
+                "Geometry must be a Point, LineString or LinearRing".to_string(),
+            )),
         }
     }
 
-    fn _area(obj: *const c_void) -> f64 {
-        let n_mut_ref = &mut 0.0;
-        let ret_val = unsafe { GEOSArea(obj, n_mut_ref as *mut c_double) };
-        assert!(ret_val != 0);
-        return *n_mut_ref;
+    pub fn area(&self) -> Result<f64, GeosError> {
+        let mut area = 0.;
+        let ret_val = unsafe { GEOSArea_r(handle(), self.c_obj as *const c_void, &mut area) };
+        check_double(ret_val, area, "GEOSArea")
     }
 
-    pub fn to_wkt(&self) -> String {
-        unsafe { _string(GEOSGeomToWKT(self.c_obj as *const c_void)) }
+    /// Returns the length of the lines, or the perimeter of the polygons.
+    pub fn length(&self) -> Result<f64, GeosError> {
+        let mut length = 0.;
+        let ret_val = unsafe { GEOSLength_r(handle(), self.c_obj as *const c_void, &mut length) };
+        check_double(ret_val, length, "GEOSLength")
     }
 
-    pub fn to_wkt_precison(&self, precision: Option<u32>) -> String {
-        let writer = unsafe { GEOSWKTWriter_create() };
-        if let Some(x) = precision {
-            unsafe { GEOSWKTWriter_setRoundingPrecision(writer, x as c_int) }
-        };
-        let c_result = unsafe { GEOSWKTWriter_write(writer, self.c_obj as *const c_void) };
+    /// Returns the X coordinate of a Point.
+    pub fn get_x(&self) -> Result<f64, GeosError> {
+        let mut x = 0.;
+        let ret_val = unsafe { GEOSGeomGetX_r(handle(), self.c_obj as *const c_void, &mut x) };
+        check_double(ret_val, x, "GEOSGeomGetX")
+    }
+
+    /// Returns the Y coordinate of a Point.
+    pub fn get_y(&self) -> Result<f64, GeosError> {
+        let mut y = 0.;
+        let ret_val = unsafe { GEOSGeomGetY_r(handle(), self.c_obj as *const c_void, &mut y) };
+        check_double(ret_val, y, "GEOSGeomGetY")
+    }
+
+    /// Returns the Z coordinate of a Point, NaN if it has none.
+    #[cfg(geos_v3_7_0)]
+    pub fn get_z(&self) -> Result<f64, GeosError> {
+        let mut z = 0.;
+        let ret_val = unsafe { GEOSGeomGetZ_r(handle(), self.c_obj as *const c_void, &mut z) };
+        check_double(ret_val, z, "GEOSGeomGetZ")
+    }
+
+    pub fn to_wkt(&self) -> Result<String, GeosError> {
+        let c_result = unsafe { GEOSGeomToWKT_r(handle(), self.c_obj as *const c_void) };
+        if c_result.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSGeomToWKT returned NULL".to_string(),
+            )));
+        }
         let result = _string(c_result);
-        unsafe { GEOSWKTWriter_destroy(writer) };
-        unsafe { GEOSFree(c_result as *mut c_void) };
-        result
+        unsafe { GEOSFree_r(handle(), c_result as *mut c_void) };
+        Ok(result)
+    }
+
+    /// Writes the geometry as WKT, with the coordinates rounded to `precision` decimals.
+    pub fn to_wkt_precision(&self, precision: u32) -> Result<String, GeosError> {
+        WKTWriter::new()?
+            .rounding_precision(Some(precision))
+            .write(self)
     }
 
-    pub fn to_wkb(&self) -> (*const u8, size_t) {
+    #[deprecated(note = "Use `to_wkt_precision` instead")]
+    pub fn to_wkt_precison(&self, precision: Option<u32>) -> Result<String, GeosError> {
+        match precision {
+            Some(precision) => self.to_wkt_precision(precision),
+            None => self.to_wkt(),
+        }
+    }
+
+    pub fn geometry_type(&self) -> Result<GeometryType, GeosError> {
+        GeometryType::from_c_int(unsafe { GEOSGeomTypeId_r(handle(), self.c_obj as *const c_void) })
+    }
+
+    /// Returns the name of the type of the geometry, such as "Point" or "MultiPolygon".
+    pub fn geom_type(&self) -> Result<String, GeosError> {
+        let c_result = unsafe { GEOSGeomType_r(handle(), self.c_obj as *const c_void) };
+        if c_result.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSGeomType returned NULL".to_string(),
+            )));
+        }
+        let result = _string(c_result);
+        unsafe { GEOSFree_r(handle(), c_result as *mut c_void) };
+        Ok(result)
+    }
+
+    pub fn to_wkb(&self) -> Result<Vec<u8>, GeosError> {
         let mut dstlen: size_t = 0 as size_t;
-        (
-            unsafe { (GEOSGeomToWKB_buf(self.c_obj as *const c_void, &mut dstlen)) },
-            dstlen,
-        )
+        let wkb =
+            unsafe { GEOSGeomToWKB_buf_r(handle(), self.c_obj as *const c_void, &mut dstlen) };
+        if wkb.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSGeomToWKB_buf returned NULL".to_string(),
+            )));
+        }
+        let result = unsafe { slice::from_raw_parts(wkb, dstlen).to_vec() };
+        unsafe { GEOSFree_r(handle(), wkb as *mut c_void) };
+        Ok(result)
     }
 
-    pub fn is_ring(&self) -> bool {
-        let rv = unsafe { GEOSisRing(self.c_obj as *const c_void) };
-        return if rv == 1 { true } else { false };
+    /// Returns the SRID of the geometry, 0 when it wasn't set.
+    pub fn get_srid(&self) -> i32 {
+        unsafe { GEOSGetSRID_r(handle(), self.c_obj as *const c_void) as i32 }
     }
 
-    pub fn intersects(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSIntersects(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    /// Sets the SRID of the geometry, written in the WKB output of a `WKBWriter`
+    /// including the SRID.
+    pub fn set_srid(&mut self, srid: i32) {
+        unsafe { GEOSSetSRID_r(handle(), self.c_obj, srid as c_int) };
     }
 
-    pub fn crosses(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSCrosses(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn is_ring(&self) -> Result<bool, GeosError> {
+        let ret_val = unsafe { GEOSisRing_r(handle(), self.c_obj as *const c_void) };
+        check_predicate(ret_val, "GEOSisRing")
     }
 
-    pub fn disjoint(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSDisjoint(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn intersects(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSIntersects_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSIntersects")
     }
 
-    pub fn touches(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSTouches(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn crosses(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSCrosses_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSCrosses")
     }
 
-    pub fn overlaps(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSOverlaps(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn disjoint(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSDisjoint_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSDisjoint")
+    }
+
+    pub fn touches(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSTouches_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSTouches")
+    }
+
+    pub fn overlaps(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSOverlaps_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSOverlaps")
     }
 
-    pub fn within(&self, g2: &GGeom) -> bool {
-        let ret_val = unsafe { GEOSWithin(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn within(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSWithin_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSWithin")
     }
 
-    pub fn equals(&self, g2: &GGeom) -> bool {
-        let ret_val = unsafe { GEOSEquals(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn equals(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSEquals_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSEquals")
     }
 
-    pub fn equals_exact(&self, g2: &GGeom, precision: f64) -> bool {
+    pub fn equals_exact(&self, g2: &GGeom, precision: f64) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSEqualsExact(
+            GEOSEqualsExact_r(
+                handle(),
                 self.c_obj as *const c_void,
                 g2.c_obj as *const c_void,
                 precision as c_double,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSEqualsExact")
     }
 
-    pub fn covers(&self, g2: &GGeom) -> bool {
-        let ret_val = unsafe { GEOSCovers(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn covers(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSCovers_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSCovers")
+    }
+
+    pub fn covered_by(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSCoveredBy_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSCoveredBy")
+    }
+
+    pub fn contains(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSContains_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSContains")
+    }
+
+    /// Returns the minimum distance between the two geometries.
+    pub fn distance(&self, g2: &GGeom) -> Result<f64, GeosError> {
+        let mut distance = 0.;
+        let ret_val = unsafe {
+            GEOSDistance_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                &mut distance,
+            )
+        };
+        check_double(ret_val, distance, "GEOSDistance")
+    }
+
+    /// Returns the greatest distance from a point of one of the geometries to
+    /// the other geometry, computed on the vertices only.
+    pub fn hausdorff_distance(&self, g2: &GGeom) -> Result<f64, GeosError> {
+        let mut distance = 0.;
+        let ret_val = unsafe {
+            GEOSHausdorffDistance_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                &mut distance,
+            )
+        };
+        check_double(ret_val, distance, "GEOSHausdorffDistance")
+    }
+
+    /// Same as `hausdorff_distance`, with each segment split in smaller
+    /// segments of length `densify_frac` times its own length, in ]0, 1].
+    pub fn hausdorff_distance_densify(
+        &self,
+        g2: &GGeom,
+        densify_frac: f64,
+    ) -> Result<f64, GeosError> {
+        let mut distance = 0.;
+        let ret_val = unsafe {
+            GEOSHausdorffDistanceDensify_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                densify_frac as c_double,
+                &mut distance,
+            )
+        };
+        check_double(ret_val, distance, "GEOSHausdorffDistanceDensify")
     }
 
-    pub fn covered_by(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSCoveredBy(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    /// Returns the discrete Fréchet distance between the two geometries, which
+    /// takes the order of the vertices into account, unlike the Hausdorff distance.
+    #[cfg(geos_v3_7_0)]
+    pub fn frechet_distance(&self, g2: &GGeom) -> Result<f64, GeosError> {
+        let mut distance = 0.;
+        let ret_val = unsafe {
+            GEOSFrechetDistance_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                &mut distance,
+            )
+        };
+        check_double(ret_val, distance, "GEOSFrechetDistance")
     }
 
-    pub fn contains(&self, g2: &GGeom) -> bool {
-        let ret_val =
-            unsafe { GEOSContains(self.c_obj as *const c_void, g2.c_obj as *const c_void) };
-        return (ret_val == 1);
+    /// Same as `frechet_distance`, with each segment split in smaller
+    /// segments of length `densify_frac` times its own length, in ]0, 1].
+    #[cfg(geos_v3_7_0)]
+    pub fn frechet_distance_densify(
+        &self,
+        g2: &GGeom,
+        densify_frac: f64,
+    ) -> Result<f64, GeosError> {
+        let mut distance = 0.;
+        let ret_val = unsafe {
+            GEOSFrechetDistanceDensify_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                densify_frac as c_double,
+                &mut distance,
+            )
+        };
+        check_double(ret_val, distance, "GEOSFrechetDistanceDensify")
     }
 
-    pub fn buffer(&self, width: f64, quadsegs: i32) -> GGeom {
+    /// Returns `true` if the two geometries are within `distance` of each other,
+    /// which is faster than comparing the result of `distance`.
+    #[cfg(geos_v3_10_0)]
+    pub fn is_within_distance(&self, g2: &GGeom, distance: f64) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSDistanceWithin_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                distance as c_double,
+            )
+        };
+        check_predicate(ret_val, "GEOSDistanceWithin")
+    }
+
+    /// Returns the two closest points of the geometries, as a CoordSeq where
+    /// the first point lies on this geometry and the second one on `g2`.
+    pub fn nearest_points(&self, g2: &GGeom) -> Result<CoordSeq, GeosError> {
+        let ret_val = unsafe {
+            GEOSNearestPoints_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_coord_seq(ret_val, "GEOSNearestPoints")
+    }
+
+    pub fn buffer(&self, width: f64, quadsegs: i32) -> Result<GGeom, GeosError> {
         GGeom::new_from_c_obj(unsafe {
-            GEOSBuffer(
+            GEOSBuffer_r(
+                handle(),
                 self.c_obj as *const c_void,
                 width as c_double,
                 quadsegs as c_int,
@@ -485,146 +1555,684 @@ impl GGeom {
         })
     }
 
-    pub fn is_empty(&self) -> bool {
-        let ret_val = unsafe { GEOSisEmpty(self.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn buffer_with_style(
+        &self,
+        width: f64,
+        quadsegs: i32,
+        end_cap_style: CapStyle,
+        join_style: JoinStyle,
+        mitre_limit: f64,
+    ) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe {
+            GEOSBufferWithStyle_r(
+                handle(),
+                self.c_obj as *const c_void,
+                width as c_double,
+                quadsegs as c_int,
+                end_cap_style as c_int,
+                join_style as c_int,
+                mitre_limit as c_double,
+            )
+        })
+    }
+
+    pub fn buffer_with_params(
+        &self,
+        params: &BufferParams,
+        width: f64,
+    ) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe {
+            GEOSBufferWithParams_r(
+                handle(),
+                self.c_obj as *const c_void,
+                params.0 as *const GEOSBufferParams,
+                width as c_double,
+            )
+        })
+    }
+
+    /// Computes a line parallel to this one, at `width` on its left side
+    /// (or on its right side for a negative width).
+    pub fn offset_curve(
+        &self,
+        width: f64,
+        quadsegs: i32,
+        join_style: JoinStyle,
+        mitre_limit: f64,
+    ) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe {
+            GEOSOffsetCurve_r(
+                handle(),
+                self.c_obj as *const c_void,
+                width as c_double,
+                quadsegs as c_int,
+                join_style as c_int,
+                mitre_limit as c_double,
+            )
+        })
+    }
+
+    pub fn is_empty(&self) -> Result<bool, GeosError> {
+        let ret_val = unsafe { GEOSisEmpty_r(handle(), self.c_obj as *const c_void) };
+        check_predicate(ret_val, "GEOSisEmpty")
+    }
+
+    pub fn is_simple(&self) -> Result<bool, GeosError> {
+        let ret_val = unsafe { GEOSisSimple_r(handle(), self.c_obj as *const c_void) };
+        check_predicate(ret_val, "GEOSisSimple")
+    }
+
+    pub fn difference(&self, g2: &GGeom) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSDifference_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_overlay(ret_val, "GEOSDifference")
+    }
+
+    /// Same as `difference`, with the coordinates snapped to a grid of `grid_size`
+    /// during the computation, which avoids most of the topology errors.
+    #[cfg(geos_v3_9_0)]
+    pub fn difference_prec(&self, g2: &GGeom, grid_size: f64) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSDifferencePrec_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                grid_size as c_double,
+            )
+        };
+        check_overlay(ret_val, "GEOSDifferencePrec")
+    }
+
+    pub fn envelope(&self) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe { GEOSEnvelope_r(handle(), self.c_obj as *const c_void) })
+    }
+
+    fn bbox(&self) -> Result<Bbox<f64>, GeosError> {
+        if self.is_empty()? {
+            return Err(GeosError::InvalidArgument(
+                "An empty geometry has no envelope".to_string(),
+            ));
+        }
+        // The envelope is a Point or a Polygon, depending on its extent.
+        let mut bbox = Bbox {
+            xmin: f64::INFINITY,
+            xmax: f64::NEG_INFINITY,
+            ymin: f64::INFINITY,
+            ymax: f64::NEG_INFINITY,
+        };
+        for coord in self.envelope()?.coords()? {
+            let (x, y, _) = coord?;
+            bbox.xmin = bbox.xmin.min(x);
+            bbox.xmax = bbox.xmax.max(x);
+            bbox.ymin = bbox.ymin.min(y);
+            bbox.ymax = bbox.ymax.max(y);
+        }
+        Ok(bbox)
+    }
+
+    /// Snaps the vertices and segments of the geometry to the vertices of
+    /// `other` lying within `tolerance`.
+    pub fn snap(&self, other: &GGeom, tolerance: f64) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe {
+            GEOSSnap_r(
+                handle(),
+                self.c_obj as *const c_void,
+                other.c_obj as *const c_void,
+                tolerance as c_double,
+            )
+        })
+    }
+
+    /// Clips the geometry to a rectangle, faster than an intersection but the
+    /// result may be invalid (for example self-touching polygons).
+    pub fn clip_by_rect(
+        &self,
+        xmin: f64,
+        ymin: f64,
+        xmax: f64,
+        ymax: f64,
+    ) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe {
+            GEOSClipByRect_r(
+                handle(),
+                self.c_obj as *const c_void,
+                xmin as c_double,
+                ymin as c_double,
+                xmax as c_double,
+                ymax as c_double,
+            )
+        })
+    }
+
+    /// Clips the geometry to the envelope of `envelope`.
+    pub fn clip_by_envelope(&self, envelope: &GGeom) -> Result<GGeom, GeosError> {
+        self.clip_by_bbox(&envelope.bbox()?)
+    }
+
+    pub fn clip_by_bbox(&self, bbox: &Bbox<f64>) -> Result<GGeom, GeosError> {
+        self.clip_by_rect(bbox.xmin, bbox.ymin, bbox.xmax, bbox.ymax)
     }
 
-    pub fn is_simple(&self) -> bool {
-        let ret_val = unsafe { GEOSisSimple(self.c_obj as *const c_void) };
-        return (ret_val == 1);
+    pub fn sym_difference(&self, g2: &GGeom) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSSymDifference_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_overlay(ret_val, "GEOSSymDifference")
+    }
+
+    /// Same as `sym_difference`, computed on a grid of `grid_size`.
+    #[cfg(geos_v3_9_0)]
+    pub fn sym_difference_prec(&self, g2: &GGeom, grid_size: f64) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSSymDifferencePrec_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                grid_size as c_double,
+            )
+        };
+        check_overlay(ret_val, "GEOSSymDifferencePrec")
+    }
+
+    pub fn intersection(&self, g2: &GGeom) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSIntersection_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_overlay(ret_val, "GEOSIntersection")
+    }
+
+    /// Same as `intersection`, computed on a grid of `grid_size`.
+    #[cfg(geos_v3_9_0)]
+    pub fn intersection_prec(&self, g2: &GGeom, grid_size: f64) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSIntersectionPrec_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                grid_size as c_double,
+            )
+        };
+        check_overlay(ret_val, "GEOSIntersectionPrec")
+    }
+
+    pub fn union(&self, g2: &GGeom) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSUnion_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_overlay(ret_val, "GEOSUnion")
+    }
+
+    /// Same as `union`, computed on a grid of `grid_size`.
+    #[cfg(geos_v3_9_0)]
+    pub fn union_prec(&self, g2: &GGeom, grid_size: f64) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSUnionPrec_r(
+                handle(),
+                self.c_obj as *const c_void,
+                g2.c_obj as *const c_void,
+                grid_size as c_double,
+            )
+        };
+        check_overlay(ret_val, "GEOSUnionPrec")
+    }
+
+    /// Unions all the components of the geometry, dissolving for example
+    /// the overlapping polygons of a MultiPolygon.
+    pub fn unary_union(&self) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe { GEOSUnaryUnion_r(handle(), self.c_obj as *const c_void) };
+        check_overlay(ret_val, "GEOSUnaryUnion")
+    }
+
+    /// Same as `unary_union`, computed on a grid of `grid_size`.
+    #[cfg(geos_v3_9_0)]
+    pub fn unary_union_prec(&self, grid_size: f64) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe {
+            GEOSUnaryUnionPrec_r(handle(), self.c_obj as *const c_void, grid_size as c_double)
+        };
+        check_overlay(ret_val, "GEOSUnaryUnionPrec")
     }
-    pub fn difference(&self, g2: &GGeom) -> GGeom {
+
+    /// Unions the polygons of a MultiPolygon, prefer `unary_union` which
+    /// accepts any kind of geometry.
+    pub fn union_cascaded(&self) -> Result<GGeom, GeosError> {
+        let ret_val = unsafe { GEOSUnionCascaded_r(handle(), self.c_obj as *const c_void) };
+        check_overlay(ret_val, "GEOSUnionCascaded")
+    }
+
+    pub fn get_centroid(&self) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe { GEOSGetCentroid_r(handle(), self.c_obj as *const c_void) })
+    }
+
+    /// Returns a point guaranteed to lie on the geometry (in the interior of
+    /// polygons), unlike the centroid.
+    pub fn point_on_surface(&self) -> Result<GGeom, GeosError> {
         GGeom::new_from_c_obj(unsafe {
-            GEOSDifference(self.c_obj as *const c_void, g2.c_obj as *const c_void)
+            GEOSPointOnSurface_r(handle(), self.c_obj as *const c_void)
         })
     }
 
-    pub fn envelope(&self) -> GGeom {
-        GGeom::new_from_c_obj(unsafe { GEOSEnvelope(self.c_obj as *const c_void) })
+    pub fn convex_hull(&self) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe { GEOSConvexHull_r(handle(), self.c_obj as *const c_void) })
     }
 
-    pub fn sym_difference(&self, g2: &GGeom) -> GGeom {
+    pub fn boundary(&self) -> Result<GGeom, GeosError> {
+        GGeom::new_from_c_obj(unsafe { GEOSBoundary_r(handle(), self.c_obj as *const c_void) })
+    }
+
+    /// Returns the distinct vertices of the geometry, as a MultiPoint.
+    pub fn extract_unique_points(&self) -> Result<GGeom, GeosError> {
         GGeom::new_from_c_obj(unsafe {
-            GEOSSymDifference(self.c_obj as *const c_void, g2.c_obj as *const c_void)
+            GEOSGeom_extractUniquePoints_r(handle(), self.c_obj as *const c_void)
         })
     }
+}
 
-    pub fn get_centroid(&self) -> GGeom {
-        GGeom::new_from_c_obj(unsafe { GEOSGetCentroid(self.c_obj as *const c_void) })
+/// Writes the geometry as WKT, a precision (as in `{:.2}`) being used
+/// as the number of decimals the coordinates are rounded to.
+///
+/// If GEOS fails, a placeholder is written so that `to_string()` doesn't panic:
+/// use `to_wkt()` to handle the error.
+impl fmt::Display for GGeom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use std::fmt::Write;
+
+        let wkt = match f.precision() {
+            Some(precision) => self.to_wkt_precision(precision as u32),
+            None => self.to_wkt(),
+        };
+        let wkt = match wkt {
+            Ok(wkt) => wkt,
+            Err(err) => format!("<invalid geometry: {}>", err),
+        };
+        // Padded here, as `Formatter::pad` would truncate the WKT to `precision` characters.
+        let padding = f.width().unwrap_or(0).saturating_sub(wkt.chars().count());
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        f.write_str(&wkt)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
     }
 }
 
-pub struct PreparedGGeom(*mut GEOSPreparedGeometry);
+impl fmt::Debug for GGeom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.geom_type(), self.to_wkt()) {
+            (Ok(type_name), Ok(wkt)) => f
+                .debug_struct("GGeom")
+                .field("type", &type_name)
+                .field("wkt", &wkt)
+                .finish(),
+            (Err(err), _) | (_, Err(err)) => f.debug_struct("GGeom").field("error", &err).finish(),
+        }
+    }
+}
 
-impl Clone for PreparedGGeom {
-    fn clone(&self) -> PreparedGGeom {
-        PreparedGGeom(self.0)
+/// A geometry owned by another one, such as the ring of a Polygon or a part of a
+/// collection, borrowed for as long as its parent is.
+///
+/// It derefs to `GGeom` to give access to all the methods of the geometries,
+/// and `clone()` returns an owned copy.
+pub struct GGeomRef<'a> {
+    geom: ManuallyDrop<GGeom>,
+    parent: PhantomData<&'a GGeom>,
+}
+
+impl<'a> GGeomRef<'a> {
+    fn new(g: *const c_void, func: &str) -> Result<GGeomRef<'a>, GeosError> {
+        if g.is_null() {
+            return Err(last_error(GeosError::NullReturn(format!(
+                "{} returned NULL",
+                func
+            ))));
+        }
+        Ok(GGeomRef {
+            // The GGeom is never dropped, so the parent stays the only owner.
+            geom: ManuallyDrop::new(GGeom {
+                c_obj: g as *mut c_void,
+            }),
+            parent: PhantomData,
+        })
+    }
+}
+
+impl<'a> Deref for GGeomRef<'a> {
+    type Target = GGeom;
+
+    fn deref(&self) -> &GGeom {
+        &self.geom
+    }
+}
+
+impl<'a> fmt::Display for GGeomRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.geom, f)
     }
 }
 
-impl Drop for PreparedGGeom {
+impl<'a> fmt::Debug for GGeomRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.geom, f)
+    }
+}
+
+/// The coordinates of a geometry, borrowed for as long as the geometry is.
+///
+/// It derefs to `CoordSeq` to read the coordinates, and `clone()` returns an
+/// owned copy which can be modified. It can't outlive the geometry:
+///
+/// ```compile_fail,E0505
+/// # use geos::GGeom;
+/// let point = GGeom::new("POINT (2.5 3.5)").unwrap();
+/// let seq = point.get_coord_seq().unwrap();
+/// drop(point);
+/// seq.get_x(0).unwrap();
+/// ```
+///
+/// nor modify its coordinates:
+///
+/// ```compile_fail,E0596
+/// # use geos::GGeom;
+/// let point = GGeom::new("POINT (2.5 3.5)").unwrap();
+/// let mut seq = point.get_coord_seq().unwrap();
+/// seq.set_x(0, 1.).unwrap();
+/// ```
+pub struct CoordSeqRef<'a> {
+    seq: ManuallyDrop<CoordSeq>,
+    geom: PhantomData<&'a GGeom>,
+}
+
+impl<'a> Deref for CoordSeqRef<'a> {
+    type Target = CoordSeq;
+
+    fn deref(&self) -> &CoordSeq {
+        &self.seq
+    }
+}
+
+/// Iterator over the parts of a geometry, see `GGeom::parts`.
+///
+/// The parts are all fetched by `GGeom::parts`, so iterating can't fail.
+pub struct Parts<'a>(vec::IntoIter<GGeomRef<'a>>);
+
+impl<'a> Iterator for Parts<'a> {
+    type Item = GGeomRef<'a>;
+
+    fn next(&mut self) -> Option<GGeomRef<'a>> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Parts<'a> {}
+
+/// Iterator over the rings of a Polygon, see `GGeom::rings`.
+///
+/// The rings are all fetched by `GGeom::rings`, so iterating can't fail.
+pub struct Rings<'a>(vec::IntoIter<GGeomRef<'a>>);
+
+impl<'a> Iterator for Rings<'a> {
+    type Item = GGeomRef<'a>;
+
+    fn next(&mut self) -> Option<GGeomRef<'a>> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Rings<'a> {}
+
+struct CoordSeqInfo {
+    s: *const GEOSCoordSequence,
+    size: c_uint,
+    dims: c_uint,
+}
+
+impl CoordSeqInfo {
+    fn new(s: *const GEOSCoordSequence) -> Result<CoordSeqInfo, GeosError> {
+        let size = coord_seq_size(s)?;
+        let mut dims: c_uint = 0;
+        if unsafe { GEOSCoordSeq_getDimensions_r(handle(), s, &mut dims) } == 0 {
+            return Err(last_error(GeosError::GenericError(
+                "GEOSCoordSeq_getDimensions failed".to_string(),
+            )));
+        }
+        Ok(CoordSeqInfo { s, size, dims })
+    }
+}
+
+/// Iterator over the coordinates of a geometry or of a CoordSeq,
+/// see `GGeom::coords` and `CoordSeq::iter`.
+///
+/// The Z coordinate is `None` when the geometry has no Z dimension. The
+/// iteration stops after the first error returned by GEOS.
+pub struct Coords<'a> {
+    seqs: Vec<CoordSeqInfo>,
+    seq_index: usize,
+    coord_index: c_uint,
+    geom: PhantomData<&'a ()>,
+}
+
+impl<'a> Iterator for Coords<'a> {
+    type Item = Result<(f64, f64, Option<f64>), GeosError>;
+
+    fn next(&mut self) -> Option<Result<(f64, f64, Option<f64>), GeosError>> {
+        while self.seq_index < self.seqs.len() && self.coord_index >= self.seqs[self.seq_index].size
+        {
+            self.seq_index += 1;
+            self.coord_index = 0;
+        }
+        let seq = self.seqs.get(self.seq_index)?;
+        let idx = self.coord_index;
+        self.coord_index += 1;
+
+        let (mut x, mut y, mut z) = (0., 0., f64::NAN);
+        unsafe {
+            if GEOSCoordSeq_getX_r(handle(), seq.s, idx, &mut x) == 0
+                || GEOSCoordSeq_getY_r(handle(), seq.s, idx, &mut y) == 0
+                || (seq.dims > 2 && GEOSCoordSeq_getZ_r(handle(), seq.s, idx, &mut z) == 0)
+            {
+                self.seq_index = self.seqs.len();
+                return Some(Err(last_error(GeosError::GenericError(
+                    "Unable to get the coordinates of the CoordSeq".to_string(),
+                ))));
+            }
+        }
+        // A sequence with 3 dimensions may still hold 2D coordinates, with a NaN Z.
+        Some(Ok((x, y, if z.is_nan() { None } else { Some(z) })))
+    }
+}
+
+/// A geometry prepared for faster predicates, when testing it against many others.
+///
+/// GEOS keeps pointing to the source geometry, so it stays borrowed for as long
+/// as the prepared geometry lives:
+///
+/// ```compile_fail,E0505
+/// # use geos::GGeom;
+/// let geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+/// let prepared = geom.to_prepared().unwrap();
+/// drop(geom);
+/// prepared.intersects(&GGeom::new("POINT (1 1)").unwrap());
+/// ```
+///
+/// It can't be sent to another thread either, as the source geometry could then
+/// be used from two threads at once:
+///
+/// ```compile_fail,E0277
+/// # use geos::GGeom;
+/// # use std::thread;
+/// let geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+/// let prepared = geom.to_prepared().unwrap();
+/// thread::scope(|scope| {
+///     scope.spawn(move || prepared.intersects(&GGeom::new("POINT (1 1)").unwrap()));
+///     geom.area().unwrap();
+/// });
+/// ```
+pub struct PreparedGGeom<'a>(*mut GEOSPreparedGeometry, PhantomData<&'a GGeom>);
+
+impl<'a> Drop for PreparedGGeom<'a> {
     fn drop(&mut self) {
         if self.0.is_null() {
             return;
         }
-        unsafe { GEOSPreparedGeom_destroy(self.0) };
+        with_drop_handle(|handle| unsafe { GEOSPreparedGeom_destroy_r(handle, self.0) });
         self.0 = ptr::null_mut();
     }
 }
 
-impl PreparedGGeom {
-    pub fn new(g: &GGeom) -> PreparedGGeom {
-        PreparedGGeom(unsafe { GEOSPrepare(g.c_obj) })
+impl<'a> PreparedGGeom<'a> {
+    pub fn new(g: &'a GGeom) -> Result<PreparedGGeom<'a>, GeosError> {
+        let c_obj = unsafe { GEOSPrepare_r(handle(), g.c_obj) };
+        if c_obj.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSPrepare returned NULL".to_string(),
+            )));
+        }
+        Ok(PreparedGGeom(c_obj, PhantomData))
+    }
+    pub fn contains(&self, g2: &GGeom) -> Result<bool, GeosError> {
+        let ret_val = unsafe {
+            GEOSPreparedContains_r(
+                handle(),
+                self.0 as *const GEOSPreparedGeometry,
+                g2.c_obj as *const c_void,
+            )
+        };
+        check_predicate(ret_val, "GEOSPreparedContains")
     }
-    pub fn contains(&self, g2: &GGeom) -> bool {
+    pub fn contains_properly(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedContains(
+            GEOSPreparedContainsProperly_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedContainsProperly")
     }
-    pub fn contains_properly(&self, g2: &GGeom) -> bool {
+    pub fn covered_by(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedContainsProperly(
+            GEOSPreparedCoveredBy_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedCoveredBy")
     }
-    pub fn covered_by(&self, g2: &GGeom) -> bool {
+    pub fn covers(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedCoveredBy(
+            GEOSPreparedCovers_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedCovers")
     }
-    pub fn covers(&self, g2: &GGeom) -> bool {
+    pub fn crosses(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedCovers(
+            GEOSPreparedCrosses_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedCrosses")
     }
-    pub fn crosses(&self, g2: &GGeom) -> bool {
+    pub fn disjoint(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedCrosses(
+            GEOSPreparedDisjoint_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedDisjoint")
     }
-    pub fn disjoint(&self, g2: &GGeom) -> bool {
+    pub fn intersects(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedDisjoint(
+            GEOSPreparedIntersects_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedIntersects")
     }
-    pub fn intersects(&self, g2: &GGeom) -> bool {
+    pub fn overlaps(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedIntersects(
+            GEOSPreparedOverlaps_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedOverlaps")
     }
-    pub fn overlaps(&self, g2: &GGeom) -> bool {
+    pub fn touches(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedOverlaps(
+            GEOSPreparedTouches_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedTouches")
     }
-    pub fn touches(&self, g2: &GGeom) -> bool {
+    /// Returns the two closest points of the prepared geometry and `g2`, the
+    /// first one lying on the prepared geometry.
+    #[cfg(geos_v3_9_0)]
+    pub fn nearest_points(&self, g2: &GGeom) -> Result<CoordSeq, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedTouches(
+            GEOSPreparedNearestPoints_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_coord_seq(ret_val, "GEOSPreparedNearestPoints")
     }
-    pub fn within(&self, g2: &GGeom) -> bool {
+    pub fn within(&self, g2: &GGeom) -> Result<bool, GeosError> {
         let ret_val = unsafe {
-            GEOSPreparedWithin(
+            GEOSPreparedWithin_r(
+                handle(),
                 self.0 as *const GEOSPreparedGeometry,
                 g2.c_obj as *const c_void,
             )
         };
-        return (ret_val == 1);
+        check_predicate(ret_val, "GEOSPreparedWithin")
     }
 }
@@ -1,38 +1,112 @@
 extern crate geo;
 
 use libc::{c_int, c_uint};
-use self::geo::{LineString, MultiPolygon, Polygon};
-use ffi::{CoordSeq, GEOSGeomTypes, GEOSGeom_clone, GEOSGeom_createCollection,
-          GEOSGeom_createPolygon, GGeom, _linearRing};
-
-impl<'a> From<&'a LineString<f64>> for GGeom {
-    fn from(ls: &LineString<f64>) -> Self {
-        let nb_pts = ls.0.len();
-        let coord_seq_ext = CoordSeq::new(nb_pts as u32, 2);
-        for i in 0..nb_pts {
-            let j = i as u32;
-            coord_seq_ext.set_x(j, ls.0[i].x());
-            coord_seq_ext.set_y(j, ls.0[i].y());
-        }
-        _linearRing(&coord_seq_ext)
+use self::geo::{Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+                MultiPolygon, Point, Polygon};
+use std::convert::TryFrom;
+use context_handle::handle;
+use error::GeosError;
+use ffi::{CoordSeq, GEOSGeom_clone_r, GEOSGeom_createCollection_r, GEOSGeom_createPolygon_r,
+          GeometryType, GGeom, _lineString, _linearRing, _point};
+
+fn coord_seq(points: &[Point<f64>]) -> Result<CoordSeq, GeosError> {
+    let coords: Vec<_> = points.iter().map(|pt| [pt.x(), pt.y()]).collect();
+    CoordSeq::from_slice(&coords)
+}
+
+fn create_collection(type_geom: GeometryType, geoms: &[GGeom]) -> Result<GGeom, GeosError> {
+    let nb_geoms = geoms.len();
+    let mut geoms: Vec<_> = geoms
+        .iter()
+        .map(|g| unsafe { GEOSGeom_clone_r(handle(), g.c_obj) })
+        .collect();
+
+    let t = unsafe {
+        GEOSGeom_createCollection_r(
+            handle(),
+            type_geom as c_int,
+            geoms.as_mut_ptr(),
+            nb_geoms as c_uint,
+        )
+    };
+    GGeom::new_from_c_obj(t)
+}
+
+impl TryFrom<&Point<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(pt: &Point<f64>) -> Result<GGeom, GeosError> {
+        _point(&coord_seq(&[*pt])?)
     }
 }
 
-impl<'a> From<&'a Polygon<f64>> for GGeom {
-    fn from(p: &Polygon<f64>) -> Self {
-        let geom_exterior: GGeom = (&p.exterior).into();
+impl TryFrom<&MultiPoint<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(mp: &MultiPoint<f64>) -> Result<GGeom, GeosError> {
+        let points = mp.0
+            .iter()
+            .map(GGeom::try_from)
+            .collect::<Result<Vec<GGeom>, GeosError>>()?;
+        create_collection(GeometryType::MultiPoint, &points)
+    }
+}
+
+/// A closed `LineString` to convert into a GEOS LinearRing, as used for the
+/// exterior and interior rings of polygons.
+///
+/// A `LineString` converted on its own always becomes a GEOS LineString.
+pub struct LinearRing<'a>(pub &'a LineString<f64>);
+
+impl TryFrom<&LineString<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(ls: &LineString<f64>) -> Result<GGeom, GeosError> {
+        _lineString(&coord_seq(&ls.0)?)
+    }
+}
+
+impl TryFrom<LinearRing<'_>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(ring: LinearRing<'_>) -> Result<GGeom, GeosError> {
+        _linearRing(&coord_seq(&(ring.0).0)?)
+    }
+}
+
+impl TryFrom<&MultiLineString<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(ml: &MultiLineString<f64>) -> Result<GGeom, GeosError> {
+        let lines = ml.0
+            .iter()
+            .map(GGeom::try_from)
+            .collect::<Result<Vec<GGeom>, GeosError>>()?;
+        create_collection(GeometryType::MultiLineString, &lines)
+    }
+}
+
+impl TryFrom<&Polygon<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(p: &Polygon<f64>) -> Result<GGeom, GeosError> {
+        let geom_exterior = GGeom::try_from(LinearRing(&p.exterior))?;
         let nb_interiors = p.interiors.len();
 
-        let interiors: Vec<_> = p.interiors
+        let interiors = p.interiors
             .iter()
-            .map(|i| i.into())
-            .map(|i: GGeom| unsafe { GEOSGeom_clone(i.c_obj) })
+            .map(|i| GGeom::try_from(LinearRing(i)))
+            .collect::<Result<Vec<GGeom>, GeosError>>()?;
+        let mut interiors: Vec<_> = interiors
+            .iter()
+            .map(|i| unsafe { GEOSGeom_clone_r(handle(), i.c_obj) })
             .collect();
 
         let t = unsafe {
-            GEOSGeom_createPolygon(
-                GEOSGeom_clone(geom_exterior.c_obj),
-                &interiors[..],
+            GEOSGeom_createPolygon_r(
+                handle(),
+                GEOSGeom_clone_r(handle(), geom_exterior.c_obj),
+                interiors.as_mut_ptr(),
                 nb_interiors as c_uint,
             )
         };
@@ -40,30 +114,53 @@ impl<'a> From<&'a Polygon<f64>> for GGeom {
     }
 }
 
-impl<'a> From<&'a MultiPolygon<f64>> for GGeom {
-    fn from(mp: &MultiPolygon<f64>) -> Self {
-        let nb_polygons = mp.0.len();
-        let polygons: Vec<_> = mp.0
+impl TryFrom<&MultiPolygon<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(mp: &MultiPolygon<f64>) -> Result<GGeom, GeosError> {
+        let polygons = mp.0
             .iter()
-            .map(|p| p.into())
-            .map(|g: GGeom| unsafe { GEOSGeom_clone(g.c_obj) })
-            .collect();
+            .map(GGeom::try_from)
+            .collect::<Result<Vec<GGeom>, GeosError>>()?;
+        create_collection(GeometryType::MultiPolygon, &polygons)
+    }
+}
 
-        let t = unsafe {
-            GEOSGeom_createCollection(
-                GEOSGeomTypes::GEOS_MULTIPOLYGON as c_int,
-                &polygons[..],
-                nb_polygons as c_uint,
-            )
-        };
-        GGeom::new_from_c_obj(t)
+impl TryFrom<&GeometryCollection<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(gc: &GeometryCollection<f64>) -> Result<GGeom, GeosError> {
+        let geoms = gc.0
+            .iter()
+            .map(GGeom::try_from)
+            .collect::<Result<Vec<GGeom>, GeosError>>()?;
+        create_collection(GeometryType::GeometryCollection, &geoms)
+    }
+}
+
+impl TryFrom<&Geometry<f64>> for GGeom {
+    type Error = GeosError;
+
+    fn try_from(geom: &Geometry<f64>) -> Result<GGeom, GeosError> {
+        match *geom {
+            Geometry::Point(ref g) => GGeom::try_from(g),
+            Geometry::LineString(ref g) => GGeom::try_from(g),
+            Geometry::Polygon(ref g) => GGeom::try_from(g),
+            Geometry::MultiPoint(ref g) => GGeom::try_from(g),
+            Geometry::MultiLineString(ref g) => GGeom::try_from(g),
+            Geometry::MultiPolygon(ref g) => GGeom::try_from(g),
+            Geometry::GeometryCollection(ref g) => GGeom::try_from(g),
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use from_geo::geo::{LineString, MultiPolygon, Point, Polygon};
-    use ffi::GGeom;
+    use from_geo::geo::{Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+                        MultiPolygon, Point, Polygon};
+    use from_geo::LinearRing;
+    use ffi::{GGeom, GeometryType};
+    use std::convert::TryFrom;
 
     #[test]
     fn polygon_contains_test() {
@@ -88,13 +185,47 @@ mod test {
         assert_eq!(p.exterior, exterior);
         assert_eq!(p.interiors, interiors);
 
-        let geom: GGeom = (&p).into();
+        let geom = GGeom::try_from(&p).unwrap();
+        let exterior_geom = GGeom::try_from(&exterior).unwrap();
 
-        assert!(geom.contains(&geom));
-        assert!(!geom.contains(&(&exterior).into()));
+        assert!(geom.contains(&geom).unwrap());
+        assert!(!geom.contains(&exterior_geom).unwrap());
 
-        assert!(geom.covers((&(&exterior).into())));
-        assert!(geom.touches(&(&exterior).into()));
+        assert!(geom.covers(&exterior_geom).unwrap());
+        assert!(geom.touches(&exterior_geom).unwrap());
+    }
+
+    #[test]
+    fn linestring_test() {
+        let line = LineString(vec![Point::new(0., 0.), Point::new(2., 2.), Point::new(4., 0.)]);
+        let other = GGeom::new("LINESTRING (0 1, 4 1)").unwrap();
+
+        let geom = GGeom::try_from(&line).unwrap();
+        assert_eq!(GeometryType::LineString, geom.geometry_type().unwrap());
+        assert!(!geom.is_ring().unwrap());
+        assert!(geom.crosses(&other).unwrap());
+        assert!(!geom.touches(&other).unwrap());
+
+        assert!(GGeom::try_from(LinearRing(&line)).is_err());
+    }
+
+    #[test]
+    fn linear_ring_test() {
+        let ring = LineString(vec![
+            Point::new(0., 0.),
+            Point::new(0., 1.),
+            Point::new(1., 1.),
+            Point::new(1., 0.),
+            Point::new(0., 0.),
+        ]);
+
+        let ring_geom = GGeom::try_from(LinearRing(&ring)).unwrap();
+        assert_eq!(GeometryType::LinearRing, ring_geom.geometry_type().unwrap());
+        assert!(ring_geom.is_ring().unwrap());
+
+        let line_geom = GGeom::try_from(&ring).unwrap();
+        assert_eq!(GeometryType::LineString, line_geom.geometry_type().unwrap());
+        assert!(line_geom.equals(&ring_geom).unwrap());
     }
 
     #[test]
@@ -118,9 +249,63 @@ mod test {
         let p = Polygon::new(exterior.clone(), interiors.clone());
         let mp = MultiPolygon(vec![p.clone()]);
 
-        let geom: GGeom = (&mp).into();
+        let geom = GGeom::try_from(&mp).unwrap();
+
+        assert!(geom.contains(&geom).unwrap());
+        assert!(geom.contains(&GGeom::try_from(&p).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn point_and_multipoint_test() {
+        let p = Point::new(0.5, 0.5);
+        let mp = MultiPoint(vec![Point::new(0.5, 0.5), Point::new(2., 2.)]);
+        let square = GGeom::new("POLYGON ((0 0, 0 1, 1 1, 1 0, 0 0))").unwrap();
+
+        let pt_geom = GGeom::try_from(&p).unwrap();
+        assert!(square.contains(&pt_geom).unwrap());
+        assert!(pt_geom.equals(&GGeom::new("POINT (0.5 0.5)").unwrap()).unwrap());
+
+        let mp_geom = GGeom::try_from(&mp).unwrap();
+        assert!(square.intersects(&mp_geom).unwrap());
+        assert!(!square.contains(&mp_geom).unwrap());
+        assert!(
+            mp_geom
+                .equals(&GGeom::new("MULTIPOINT ((0.5 0.5), (2 2))").unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn multilinestring_test() {
+        let ml = MultiLineString(vec![
+            LineString(vec![Point::new(0., 0.), Point::new(2., 2.)]),
+            LineString(vec![Point::new(3., 0.), Point::new(3., 3.)]),
+        ]);
+        let square = GGeom::new("POLYGON ((0 0, 0 1, 1 1, 1 0, 0 0))").unwrap();
+
+        let geom = GGeom::try_from(&ml).unwrap();
+        assert!(geom.crosses(&square).unwrap());
+        assert!(
+            geom.equals(&GGeom::new("MULTILINESTRING ((0 0, 2 2), (3 0, 3 3))").unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn geometry_collection_test() {
+        let gc = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Point(Point::new(5., 5.)),
+            Geometry::MultiPoint(MultiPoint(vec![Point::new(0.5, 0.5)])),
+        ]));
+        let square = GGeom::new("POLYGON ((0 0, 0 1, 1 1, 1 0, 0 0))").unwrap();
 
-        assert!(geom.contains(&geom));
-        assert!(geom.contains(&(&p).into()));
+        let geom = GGeom::try_from(&gc).unwrap();
+        assert_eq!(gc, Geometry::try_from(&geom).unwrap());
+        assert!(
+            GGeom::try_from(&Geometry::Point(Point::new(0.5, 0.5)))
+                .unwrap()
+                .within(&square)
+                .unwrap()
+        );
     }
 }
@@ -5,9 +5,21 @@ extern crate geo;
 extern crate libc;
 extern crate num;
 
+mod buffer_params;
+mod context_handle;
+mod error;
 mod ffi;
-pub use ffi::{_point, version, CoordSeq, GGeom, PreparedGGeom, _lineString, _linearRing};
+mod wkb;
+mod wkt;
+pub use buffer_params::{BufferParams, CapStyle, JoinStyle};
+pub use context_handle::{remove_notice_handler, set_notice_handler, with_context, GContextHandle};
+pub use error::GeosError;
+pub use ffi::{_point, version, CoordSeq, CoordSeqRef, Coords, GGeom, GGeomRef, GeometryType,
+              Parts, PreparedGGeom, Rings, _lineString, _linearRing};
+pub use wkb::{ByteOrder, OutputDimension, WKBReader, WKBWriter};
+pub use wkt::{WKTReader, WKTWriter};
 pub mod from_geo;
+pub mod to_geo;
 
 #[cfg(test)]
 mod test;
@@ -1,92 +1,805 @@
 #[cfg(test)]
 mod test {
-    use ffi::{_point, CoordSeq, GEOSGeomTypes, GGeom, PreparedGGeom, _lineString, _linearRing};
+    use buffer_params::{BufferParams, CapStyle, JoinStyle};
+    use context_handle::{handle, remove_notice_handler, set_notice_handler, with_context};
+    use error::GeosError;
+    use ffi::{GEOSGeom_getCoordSeq_r, _point, CoordSeq, GeometryType, GGeom, PreparedGGeom,
+              _lineString};
+    use wkb::{ByteOrder, OutputDimension, WKBReader, WKBWriter};
+    use wkt::{WKTReader, WKTWriter};
+    use geo::Bbox;
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
 
     #[test]
     fn test_new_geometry_from_wkt_wkb() {
-        let geom = GGeom::new("POINT (2.5 2.5)");
-        assert_eq!(GEOSGeomTypes::GEOS_POINT as i32, geom._type);
-        assert_eq!(true, geom.is_simple());
-        assert_eq!(true, geom.is_valid());
-        assert_eq!(false, geom.is_empty());
-        let line_geom = GGeom::new("LINESTRING(0.0 0.0, 7.0 7.0, 45.0 50.5, 100.0 100.0)");
-        assert_eq!(GEOSGeomTypes::GEOS_LINESTRING as i32, line_geom._type);
-        let (wkb_geom, size) = geom.to_wkb();
-        let g3 = GGeom::new_from_wkb(wkb_geom, size);
-        assert_eq!(true, g3.equals(&geom));
+        let geom = GGeom::new("POINT (2.5 2.5)").unwrap();
+        assert_eq!(GeometryType::Point, geom.geometry_type().unwrap());
+        assert_eq!(true, geom.is_simple().unwrap());
+        assert_eq!(true, geom.is_valid().unwrap());
+        assert_eq!(false, geom.is_empty().unwrap());
+        let line_geom = GGeom::new("LINESTRING(0.0 0.0, 7.0 7.0, 45.0 50.5, 100.0 100.0)").unwrap();
+        assert_eq!(GeometryType::LineString, line_geom.geometry_type().unwrap());
+        let wkb_geom = geom.to_wkb().unwrap();
+        let g3 = GGeom::from_wkb(&wkb_geom).unwrap();
+        assert_eq!(true, g3.equals(&geom).unwrap());
+        let g4 = unsafe { GGeom::new_from_wkb(wkb_geom.as_ptr(), wkb_geom.len()) }.unwrap();
+        assert_eq!(true, g4.equals(&geom).unwrap());
+    }
+
+    #[test]
+    fn test_wkb_round_trip() {
+        let geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        let wkb = geom.to_wkb().unwrap();
+        // Byte order, geometry type, number of rings and of points, then the coordinates.
+        assert_eq!(1 + 4 + 4 + 4 + 5 * 2 * 8, wkb.len());
+        assert_eq!(true, GGeom::from_wkb(&wkb).unwrap().equals(&geom).unwrap());
+
+        match GGeom::from_wkb(&wkb[..wkb.len() - 8]) {
+            Err(GeosError::ParseError(_)) => (),
+            other => panic!("expected a parse error, got {:?}", other.map(|g| g.geometry_type())),
+        }
+        assert!(GGeom::from_wkb(&[]).is_err());
+    }
+
+    #[test]
+    fn test_wkb_writer_and_reader() {
+        let mut geom = GGeom::new("POINT (1 2 3)").unwrap();
+        let reader = WKBReader::new().unwrap();
+
+        let writer = WKBWriter::new().unwrap();
+        let native = if cfg!(target_endian = "little") {
+            ByteOrder::LittleEndian
+        } else {
+            ByteOrder::BigEndian
+        };
+        assert_eq!(native, writer.get_byte_order());
+        assert_eq!(OutputDimension::TwoD, writer.get_output_dimension());
+        assert_eq!(false, writer.get_include_srid());
+
+        let writer = writer.byte_order(ByteOrder::LittleEndian);
+        assert_eq!(
+            "0101000000000000000000F03F0000000000000040",
+            writer.write_hex(&geom).unwrap()
+        );
+
+        let writer = writer.byte_order(ByteOrder::BigEndian);
+        let wkb = writer.write(&geom).unwrap();
+        assert_eq!(0, wkb[0]);
+        assert_eq!(1 + 4 + 2 * 8, wkb.len());
+        assert_eq!(true, reader.read(&wkb).unwrap().equals(&geom).unwrap());
+
+        let writer = writer.output_dimension(OutputDimension::ThreeD);
+        assert_eq!(1 + 4 + 3 * 8, writer.write(&geom).unwrap().len());
+
+        geom.set_srid(4326);
+        let writer = writer.include_srid(true);
+        let ewkb = writer.write(&geom).unwrap();
+        assert_eq!(1 + 4 + 4 + 3 * 8, ewkb.len());
+        let read = reader.read(&ewkb).unwrap();
+        assert_eq!(4326, read.get_srid());
+        assert_eq!(true, read.equals_exact(&geom, 0.).unwrap());
+
+        let hex = writer.write_hex(&geom).unwrap();
+        assert_eq!(ewkb.len() * 2, hex.len());
+        assert_eq!(4326, reader.read_hex(&hex).unwrap().get_srid());
+        assert_eq!(4326, reader.read_hex(&hex.to_lowercase()).unwrap().get_srid());
+
+        match reader.read_hex("0101ZZ") {
+            Err(GeosError::ParseError(_)) => (),
+            other => panic!("expected a parse error, got {:?}", other.map(|g| g.geometry_type())),
+        }
+        assert!(reader.read(&ewkb[..10]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input_returns_error() {
+        match GGeom::new("POINT (2.5") {
+            Err(GeosError::ParseError(_)) => (),
+            other => panic!("expected a parse error, got {:?}", other.map(|g| g.geometry_type())),
+        }
+        match GGeom::new("POINT (2.5 2.5)\0") {
+            Err(GeosError::InvalidArgument(_)) => (),
+            other => panic!(
+                "expected an invalid argument, got {:?}",
+                other.map(|g| g.geometry_type())
+            ),
+        }
+        assert!(GGeom::from_wkb(&[1u8, 1, 0]).is_err());
+        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        assert!(polygon_geom.get_coord_seq().is_err());
+    }
+
+    #[test]
+    fn test_try_clone() {
+        let geom = GGeom::new("LINESTRING (0 0, 5 5)").unwrap();
+        let copy = geom.try_clone().unwrap();
+        drop(geom);
+        assert_eq!(5., copy.get_point_n(1).unwrap().get_x().unwrap());
+
+        let seq = CoordSeq::from_slice(&[[1., 2.]]).unwrap();
+        let copy = seq.try_clone().unwrap();
+        drop(seq);
+        assert_eq!((1., 2.), copy.get_xy(0).unwrap());
+    }
+
+    #[test]
+    fn test_geos_messages() {
+        match GGeom::new("POINT (2.5") {
+            Err(GeosError::ParseError(msg)) => assert!(msg.starts_with("ParseException")),
+            other => panic!("expected a parse error, got {:?}", other.map(|g| g.geometry_type())),
+        }
+
+        let notices = Arc::new(Mutex::new(Vec::new()));
+        let sink = notices.clone();
+        set_notice_handler(move |msg| sink.lock().unwrap().push(msg.to_string()));
+        let bowtie = GGeom::new("POLYGON ((0 0, 1 1, 1 0, 0 1, 0 0))").unwrap();
+        assert_eq!(false, bowtie.is_valid().unwrap());
+        remove_notice_handler();
+        assert!(
+            notices
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|msg| msg.contains("Self-intersection"))
+        );
+    }
+
+    #[test]
+    fn test_ignored_error_is_cleared() {
+        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        // A failure which is never turned into a GeosError.
+        let seq = unsafe { GEOSGeom_getCoordSeq_r(handle(), polygon_geom.c_obj) };
+        assert!(seq.is_null());
+        with_context(|context| assert!(context.get_last_error().is_some()));
+        assert_eq!(25., polygon_geom.area().unwrap());
+        with_context(|context| assert_eq!(None, context.get_last_error()));
+    }
+
+    #[test]
+    fn test_context_per_thread() {
+        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let geom = polygon_geom.clone();
+                thread::spawn(move || {
+                    assert!(GGeom::new("POINT (2.5").is_err());
+                    with_context(|context| assert_eq!(None, context.get_last_error()));
+                    geom.buffer(i as f64, 8).unwrap()
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            let buffered = handle.join().unwrap();
+            assert!(buffered.area().unwrap() >= polygon_geom.area().unwrap());
+            assert_eq!(i == 0, buffered.equals(&polygon_geom).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_drop_in_thread_local_destructor() {
+        let worker = thread::spawn(|| {
+            // Initialized before the GEOS context of the thread, so destroyed after it.
+            thread_local!(static CACHE: RefCell<Vec<GGeom>> = const { RefCell::new(Vec::new()) });
+            CACHE.with(|cache| cache.borrow_mut().push(GGeom::new("POINT (1 2)").unwrap()));
+        });
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn test_metrics_and_coordinates() {
+        let square = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        assert_eq!(25., square.area().unwrap());
+        assert_eq!(20., square.length().unwrap());
+
+        let line = GGeom::new("LINESTRING (0 0, 3 4, 3 10)").unwrap();
+        assert_eq!(0., line.area().unwrap());
+        assert_eq!(11., line.length().unwrap());
+
+        let point = GGeom::new("POINT (2.5 3.5)").unwrap();
+        assert_eq!(0., point.length().unwrap());
+        assert_eq!(2.5, point.get_x().unwrap());
+        assert_eq!(3.5, point.get_y().unwrap());
+        assert!(line.get_x().is_err());
+        assert!(square.get_y().is_err());
+    }
+
+    #[test]
+    #[cfg(geos_v3_7_0)]
+    fn test_get_z() {
+        assert_eq!(3., GGeom::new("POINT (1 2 3)").unwrap().get_z().unwrap());
+        assert!(GGeom::new("POINT (1 2)").unwrap().get_z().unwrap().is_nan());
+        assert!(
+            GGeom::new("LINESTRING (0 0 0, 1 1 1)")
+                .unwrap()
+                .get_z()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_geometry_type() {
+        let cases = [
+            ("POINT (1 1)", GeometryType::Point, "Point"),
+            ("LINEARRING (0 0, 0 1, 1 1, 0 0)", GeometryType::LinearRing, "LinearRing"),
+            ("MULTIPOLYGON (((0 0, 0 1, 1 1, 0 0)))", GeometryType::MultiPolygon, "MultiPolygon"),
+            (
+                "GEOMETRYCOLLECTION (POINT (1 1))",
+                GeometryType::GeometryCollection,
+                "GeometryCollection",
+            ),
+        ];
+        for &(wkt, geometry_type, name) in &cases {
+            let geom = GGeom::new(wkt).unwrap();
+            assert_eq!(geometry_type, geom.geometry_type().unwrap());
+            assert_eq!(name, geom.geom_type().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_structural_accessors() {
+        let polygon = GGeom::new(
+            "POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0), (1 1, 2 1, 2 2, 1 1), (5 5, 6 5, 6 6, 5 5))",
+        ).unwrap();
+        assert_eq!(1, polygon.num_geometries().unwrap());
+        assert_eq!(true, polygon.get_geometry_n(0).unwrap().equals(&polygon).unwrap());
+        let exterior = polygon.exterior_ring().unwrap();
+        assert_eq!(GeometryType::LinearRing, exterior.geometry_type().unwrap());
+        assert_eq!(5, exterior.num_points().unwrap());
+        assert_eq!(2, polygon.num_interior_rings().unwrap());
+        let interiors = polygon.interior_rings().unwrap();
+        assert_eq!(2, interiors.len());
+        assert_eq!(
+            true,
+            interiors[1]
+                .equals(&GGeom::new("LINEARRING (5 5, 6 5, 6 6, 5 5)").unwrap())
+                .unwrap()
+        );
+        assert!(polygon.get_interior_ring_n(2).is_err());
+        assert!(polygon.num_points().is_err());
+
+        let point = exterior.get_point_n(2).unwrap();
+        assert_eq!(10., point.get_x().unwrap());
+        assert_eq!(10., point.get_y().unwrap());
+        assert!(exterior.get_point_n(5).is_err());
+
+        // An owned copy outlives the parent.
+        let ring = polygon.get_interior_ring_n(0).unwrap().clone();
+        drop(polygon);
+        assert_eq!(4, ring.num_points().unwrap());
+
+        let collection = GGeom::new("MULTIPOINT ((0 0), (1 1), (2 2))").unwrap();
+        assert_eq!(3, collection.num_geometries().unwrap());
+        let part = collection.get_geometry_n(1).unwrap();
+        assert_eq!("POINT (1 1)", format!("{:.0}", part));
+        assert!(collection.get_geometry_n(3).is_err());
+        assert!(collection.exterior_ring().is_err());
+    }
+
+    #[test]
+    fn test_iterators() {
+        let collection = GGeom::new(
+            "GEOMETRYCOLLECTION (POINT (1 2), MULTILINESTRING ((0 0, 1 1), (2 2, 3 3)), \
+             POLYGON ((0 0, 0 4, 4 4, 0 0), (1 2, 1 3, 2 3, 1 2)))",
+        ).unwrap();
+        let parts: Vec<_> = collection.parts().unwrap().collect();
+        assert_eq!(3, parts.len());
+        assert_eq!(GeometryType::MultiLineString, parts[1].geometry_type().unwrap());
+        assert_eq!(2, parts[1].parts().unwrap().count());
+        assert_eq!(1, parts[2].parts().unwrap().count());
+
+        let rings: Vec<_> = parts[2].rings().unwrap().collect();
+        assert_eq!(2, rings.len());
+        assert_eq!(4, rings[0].num_points().unwrap());
+        assert!(parts[0].rings().is_err());
+        assert!(parts[1].rings().is_err());
+        assert_eq!(3, collection.parts().unwrap().len());
+        assert_eq!(2, parts[2].rings().unwrap().len());
+
+        let coords = collection.coords().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(1 + 4 + 8, coords.len());
+        assert_eq!((1., 2., None), coords[0]);
+        assert_eq!((3., 3., None), coords[4]);
+        assert_eq!((1., 3., None), coords[10]);
+
+        let line = GGeom::new("LINESTRING (0 0 1, 5 5 2)").unwrap();
+        assert_eq!(
+            vec![(0., 0., Some(1.)), (5., 5., Some(2.))],
+            line.coords().unwrap().collect::<Result<Vec<_>, _>>().unwrap()
+        );
+        assert_eq!(0, GGeom::new("POINT EMPTY").unwrap().coords().unwrap().count());
+    }
+
+    #[test]
+    fn test_coord_seq_from_and_to_slice() {
+        let coords: Vec<_> = (0..1000).map(|i| [i as f64, (i * 2) as f64]).collect();
+        let seq = CoordSeq::from_slice(&coords).unwrap();
+        assert_eq!(1000, seq.size().unwrap());
+        assert_eq!(2, seq.dims().unwrap());
+        assert_eq!(coords, seq.to_vec().unwrap());
+        assert_eq!(998., seq.get_y(499).unwrap());
+
+        let line = _lineString(&seq).unwrap();
+        assert_eq!(1000, line.num_points().unwrap());
+        assert_eq!(
+            (999., 1998., None),
+            line.coords().unwrap().last().unwrap().unwrap()
+        );
+
+        let coords_3d = [[0., 1., 2.], [3., 4., 5.]];
+        let seq = CoordSeq::from_slice_3d(&coords_3d).unwrap();
+        assert_eq!(3, seq.dims().unwrap());
+        assert_eq!(coords_3d.to_vec(), seq.to_vec_3d().unwrap());
+        assert_eq!(vec![[0., 1.], [3., 4.]], seq.to_vec().unwrap());
+        assert_eq!(
+            vec![(0., 1., Some(2.)), (3., 4., Some(5.))],
+            seq.iter().unwrap().collect::<Result<Vec<_>, _>>().unwrap()
+        );
+        assert!(
+            CoordSeq::from_slice(&[[1., 2.]])
+                .unwrap()
+                .to_vec_3d()
+                .unwrap()[0][2]
+                .is_nan()
+        );
+
+        let empty = CoordSeq::from_slice(&[]).unwrap();
+        assert_eq!(0, empty.size().unwrap());
+        assert_eq!(0, empty.iter().unwrap().count());
+    }
+
+    #[test]
+    fn test_coord_seq_setters() {
+        let mut seq = CoordSeq::new(2, 3).unwrap();
+        seq.set_x(0, 1.).unwrap();
+        seq.set_y(0, 2.).unwrap();
+        seq.set_z(0, 3.).unwrap();
+        seq.set_xyz(1, 4., 5., 6.).unwrap();
+        assert_eq!(vec![[1., 2., 3.], [4., 5., 6.]], seq.to_vec_3d().unwrap());
+        seq.set_xy(1, 7., 8.).unwrap();
+        assert_eq!((7., 8.), seq.get_xy(1).unwrap());
+        assert_eq!(6., seq.get_z(1).unwrap());
+
+        match seq.set_x(2, 0.) {
+            Err(GeosError::InvalidArgument(_)) => (),
+            other => panic!("expected an invalid argument, got {:?}", other),
+        }
+        assert!(seq.set_xy(5, 0., 0.).is_err());
+        assert!(seq.set_xyz(2, 0., 0., 0.).is_err());
+        assert!(seq.get_x(2).is_err());
+        assert!(seq.get_xy(2).is_err());
+        assert_eq!(vec![[1., 2.], [7., 8.]], seq.to_vec().unwrap());
+
+        let point = _point(&seq).unwrap();
+        assert_eq!((1., 2., Some(3.)), point.coords().unwrap().next().unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_borrowed_coord_seq() {
+        let line = GGeom::new("LINESTRING (1 2, 3 4, 5 6)").unwrap();
+        for _ in 0..3 {
+            let seq = line.get_coord_seq().unwrap();
+            assert_eq!(3, seq.size().unwrap());
+            assert_eq!((3., 4.), seq.get_xy(1).unwrap());
+        }
+        // Dropping the borrowed sequences left the coordinates to the geometry.
+        let coords = line.get_coord_seq().unwrap().to_vec().unwrap();
+        assert_eq!(vec![[1., 2.], [3., 4.], [5., 6.]], coords);
+
+        let mut owned = line.get_coord_seq().unwrap().clone();
+        owned.set_xy(0, 10., 20.).unwrap();
+        assert_eq!((1., 2.), line.get_coord_seq().unwrap().get_xy(0).unwrap());
+        drop(line);
+        assert_eq!((10., 20.), owned.get_xy(0).unwrap());
     }
 
     #[test]
     fn test_relationship() {
-        let pt_geom = GGeom::new("POINT (2.5 2.5)");
-        let line_geom = GGeom::new("LINESTRING(1 1,10 50,20 25)");
-        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))");
+        let pt_geom = GGeom::new("POINT (2.5 2.5)").unwrap();
+        let line_geom = GGeom::new("LINESTRING(1 1,10 50,20 25)").unwrap();
+        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
 
-        assert_eq!(true, polygon_geom.covers(&pt_geom));
-        assert_eq!(true, polygon_geom.intersects(&pt_geom));
-        assert_eq!(false, polygon_geom.covered_by(&pt_geom));
-        assert_eq!(false, polygon_geom.equals(&pt_geom));
-        assert_eq!(false, polygon_geom.within(&pt_geom));
+        assert_eq!(true, polygon_geom.covers(&pt_geom).unwrap());
+        assert_eq!(true, polygon_geom.intersects(&pt_geom).unwrap());
+        assert_eq!(false, polygon_geom.covered_by(&pt_geom).unwrap());
+        assert_eq!(false, polygon_geom.equals(&pt_geom).unwrap());
+        assert_eq!(false, polygon_geom.within(&pt_geom).unwrap());
 
-        assert_eq!(false, pt_geom.covers(&polygon_geom));
-        assert_eq!(true, pt_geom.intersects(&polygon_geom));
-        assert_eq!(true, pt_geom.covered_by(&polygon_geom));
-        assert_eq!(false, pt_geom.equals(&polygon_geom));
-        assert_eq!(true, pt_geom.within(&polygon_geom));
+        assert_eq!(false, pt_geom.covers(&polygon_geom).unwrap());
+        assert_eq!(true, pt_geom.intersects(&polygon_geom).unwrap());
+        assert_eq!(true, pt_geom.covered_by(&polygon_geom).unwrap());
+        assert_eq!(false, pt_geom.equals(&polygon_geom).unwrap());
+        assert_eq!(true, pt_geom.within(&polygon_geom).unwrap());
 
-        assert_eq!(false, line_geom.covers(&pt_geom));
-        assert_eq!(false, line_geom.intersects(&pt_geom));
-        assert_eq!(false, line_geom.covered_by(&pt_geom));
-        assert_eq!(false, pt_geom.covered_by(&line_geom));
-        assert_eq!(true, line_geom.intersects(&polygon_geom));
-        assert_eq!(true, line_geom.crosses(&polygon_geom));
-        assert_eq!(false, line_geom.equals(&pt_geom));
+        assert_eq!(false, line_geom.covers(&pt_geom).unwrap());
+        assert_eq!(false, line_geom.intersects(&pt_geom).unwrap());
+        assert_eq!(false, line_geom.covered_by(&pt_geom).unwrap());
+        assert_eq!(false, pt_geom.covered_by(&line_geom).unwrap());
+        assert_eq!(true, line_geom.intersects(&polygon_geom).unwrap());
+        assert_eq!(true, line_geom.crosses(&polygon_geom).unwrap());
+        assert_eq!(false, line_geom.equals(&pt_geom).unwrap());
     }
 
     #[test]
     fn test_geom_creation_from_geoms() {
-        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))");
-        let new_geom = polygon_geom.buffer(100.0, 12);
-        let g1 = new_geom.difference(&polygon_geom);
-        let g2 = polygon_geom.sym_difference(&new_geom);
-        let g3 = new_geom.sym_difference(&polygon_geom);
-        assert_almost_eq(g1.area, g2.area);
-        assert_almost_eq(g2.area, g3.area);
-        let g4 = g3.get_centroid();
-        assert_eq!(GEOSGeomTypes::GEOS_POINT as i32, g4._type);
-        let g5 = g4.buffer(200.0, 12);
+        let polygon_geom = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        let new_geom = polygon_geom.buffer(100.0, 12).unwrap();
+        let g1 = new_geom.difference(&polygon_geom).unwrap();
+        let g2 = polygon_geom.sym_difference(&new_geom).unwrap();
+        let g3 = new_geom.sym_difference(&polygon_geom).unwrap();
+        assert_almost_eq(g1.area().unwrap(), g2.area().unwrap());
+        assert_almost_eq(g2.area().unwrap(), g3.area().unwrap());
+        let g4 = g3.get_centroid().unwrap();
+        assert_eq!(GeometryType::Point, g4.geometry_type().unwrap());
+        let g5 = g4.buffer(200.0, 12).unwrap();
+
+        assert!(g5.area().unwrap() > g4.area().unwrap());
+        assert_eq!(GeometryType::Polygon, g5.geometry_type().unwrap());
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let g1 = GGeom::new("POLYGON ((0 0, 0 4, 4 4, 4 0, 0 0))").unwrap();
+        let g2 = GGeom::new("POLYGON ((2 2, 2 6, 6 6, 6 2, 2 2))").unwrap();
+
+        let inter = g1.intersection(&g2).unwrap();
+        assert_eq!(GeometryType::Polygon, inter.geometry_type().unwrap());
+        assert_almost_eq(4., inter.area().unwrap());
+        assert_eq!(
+            true,
+            inter
+                .equals(&GGeom::new("POLYGON ((2 2, 2 4, 4 4, 4 2, 2 2))").unwrap())
+                .unwrap()
+        );
+
+        let union = g1.union(&g2).unwrap();
+        assert_eq!(GeometryType::Polygon, union.geometry_type().unwrap());
+        assert_almost_eq(28., union.area().unwrap());
+
+        let parcels = GGeom::new(
+            "MULTIPOLYGON (((0 0, 0 4, 4 4, 4 0, 0 0)), ((2 2, 2 6, 6 6, 6 2, 2 2)))",
+        ).unwrap();
+        let dissolved = parcels.unary_union().unwrap();
+        assert_eq!(true, dissolved.equals(&union).unwrap());
+        let cascaded = parcels.union_cascaded().unwrap();
+        assert_eq!(true, cascaded.equals(&union).unwrap());
+
+        let far = GGeom::new("POINT (10 10)").unwrap();
+        assert_eq!(true, g1.intersection(&far).unwrap().is_empty().unwrap());
+    }
+
+    #[test]
+    #[cfg(geos_v3_9_0)]
+    fn test_overlay_with_grid_size() {
+        let g1 = GGeom::new("POLYGON ((0 0, 0 4, 4 4, 4 0, 0 0))").unwrap();
+        let g2 = GGeom::new("POLYGON ((2.0000001 2, 2 6, 6 6, 6 2, 2.0000001 2))").unwrap();
+        let snapped = GGeom::new("POLYGON ((2 2, 2 6, 6 6, 6 2, 2 2))").unwrap();
+
+        let inter = g1.intersection_prec(&g2, 1.).unwrap();
+        assert_eq!(
+            true,
+            inter
+                .equals(&g1.intersection(&snapped).unwrap())
+                .unwrap()
+        );
+        assert_almost_eq(28., g1.union_prec(&g2, 1.).unwrap().area().unwrap());
+        assert_almost_eq(12., g1.difference_prec(&g2, 1.).unwrap().area().unwrap());
+        assert_almost_eq(24., g1.sym_difference_prec(&g2, 1.).unwrap().area().unwrap());
+
+        let parcels = GGeom::new(
+            "MULTIPOLYGON (((0 0, 0 4, 4 4, 4 0, 0 0)), ((2.0000001 2, 2 6, 6 6, 6 2, 2.0000001 2)))",
+        ).unwrap();
+        assert_eq!(
+            true,
+            parcels
+                .unary_union_prec(1.)
+                .unwrap()
+                .equals(&g1.union(&snapped).unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_buffer_styles() {
+        let line = GGeom::new("LINESTRING (0 0, 10 0)").unwrap();
+        let square = GGeom::new("POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0))").unwrap();
+
+        let flat = line
+            .buffer_with_style(1., 8, CapStyle::Flat, JoinStyle::Round, 5.)
+            .unwrap();
+        assert_almost_eq(20., flat.area().unwrap());
+        let squared = line
+            .buffer_with_style(1., 8, CapStyle::Square, JoinStyle::Round, 5.)
+            .unwrap();
+        assert_almost_eq(24., squared.area().unwrap());
+        let round = line
+            .buffer_with_style(1., 8, CapStyle::Round, JoinStyle::Round, 5.)
+            .unwrap();
+        let round_area = round.area().unwrap();
+        assert!(round_area > 20. + 3.1 && round_area < 20. + ::std::f64::consts::PI);
+
+        let mitre = square
+            .buffer_with_style(1., 8, CapStyle::Round, JoinStyle::Mitre, 5.)
+            .unwrap();
+        assert_almost_eq(144., mitre.area().unwrap());
+        let bevel = square
+            .buffer_with_style(1., 8, CapStyle::Round, JoinStyle::Bevel, 5.)
+            .unwrap();
+        assert_almost_eq(142., bevel.area().unwrap());
+
+        let params = BufferParams::new()
+            .unwrap()
+            .end_cap_style(CapStyle::Flat)
+            .unwrap()
+            .join_style(JoinStyle::Mitre)
+            .unwrap()
+            .single_sided(true)
+            .unwrap();
+        let left = line.buffer_with_params(&params, 1.).unwrap();
+        assert_almost_eq(10., left.area().unwrap());
+        assert_eq!(
+            true,
+            left.equals(&GGeom::new("POLYGON ((0 0, 0 1, 10 1, 10 0, 0 0))").unwrap())
+                .unwrap()
+        );
+        let params = BufferParams::new()
+            .unwrap()
+            .quadrant_segments(2)
+            .unwrap()
+            .mitre_limit(2.)
+            .unwrap();
+        let coarse = line.buffer_with_params(&params, 1.).unwrap();
+        assert!(coarse.area().unwrap() < round_area);
+    }
+
+    #[test]
+    fn test_offset_curve() {
+        let line = GGeom::new("LINESTRING (0 0, 10 0)").unwrap();
+        let left = line.offset_curve(1., 8, JoinStyle::Mitre, 5.).unwrap();
+        assert_eq!(GeometryType::LineString, left.geometry_type().unwrap());
+        assert_eq!(
+            true,
+            left.equals(&GGeom::new("LINESTRING (0 1, 10 1)").unwrap())
+                .unwrap()
+        );
+        let right = line.offset_curve(-1., 8, JoinStyle::Mitre, 5.).unwrap();
+        assert_eq!(
+            true,
+            right
+                .equals(&GGeom::new("LINESTRING (0 -1, 10 -1)").unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_derived_geometries() {
+        let horseshoe =
+            GGeom::new("POLYGON ((0 0, 0 10, 10 10, 10 0, 8 0, 8 8, 2 8, 2 0, 0 0))").unwrap();
+        let centroid = horseshoe.get_centroid().unwrap();
+        assert_eq!(false, horseshoe.contains(&centroid).unwrap());
+        let label = horseshoe.point_on_surface().unwrap();
+        assert_eq!(GeometryType::Point, label.geometry_type().unwrap());
+        assert_eq!(true, horseshoe.contains(&label).unwrap());
+
+        let hull = horseshoe.convex_hull().unwrap();
+        assert_almost_eq(100., hull.area().unwrap());
+        assert_eq!(true, hull.covers(&horseshoe).unwrap());
+
+        let square = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        let boundary = square.boundary().unwrap();
+        assert_eq!(GeometryType::LineString, boundary.geometry_type().unwrap());
+        assert_eq!(
+            true,
+            boundary
+                .equals(&GGeom::new("LINESTRING (0 0, 0 5, 5 5, 5 0, 0 0)").unwrap())
+                .unwrap()
+        );
 
-        assert!(g5.area > g4.area);
-        assert_eq!(GEOSGeomTypes::GEOS_POLYGON as i32, g5._type);
+        let points = square.extract_unique_points().unwrap();
+        assert_eq!(GeometryType::MultiPoint, points.geometry_type().unwrap());
+        assert_eq!(
+            true,
+            points
+                .equals(&GGeom::new("MULTIPOINT ((0 0), (0 5), (5 5), (5 0))").unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_snap() {
+        let line = GGeom::new("LINESTRING (0 0, 10 0.05, 20 0)").unwrap();
+        let reference = GGeom::new("POINT (10 0)").unwrap();
+        let snapped = line.snap(&reference, 0.1).unwrap();
+        assert_eq!(
+            true,
+            snapped
+                .equals(&GGeom::new("LINESTRING (0 0, 10 0, 20 0)").unwrap())
+                .unwrap()
+        );
+        let unchanged = line.snap(&reference, 0.01).unwrap();
+        assert_eq!(true, unchanged.equals(&line).unwrap());
+    }
+
+    #[test]
+    fn test_clip_by_rect() {
+        let line = GGeom::new("LINESTRING (-5 5, 15 5)").unwrap();
+        let expected = GGeom::new("LINESTRING (0 5, 10 5)").unwrap();
+        let tile = GGeom::new("POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0))").unwrap();
+        let bbox = Bbox {
+            xmin: 0.,
+            xmax: 10.,
+            ymin: 0.,
+            ymax: 10.,
+        };
+
+        let clipped = line.clip_by_rect(0., 0., 10., 10.).unwrap();
+        assert_eq!(true, clipped.equals(&expected).unwrap());
+        let clipped = line.clip_by_bbox(&bbox).unwrap();
+        assert_eq!(true, clipped.equals(&expected).unwrap());
+        let clipped = line.clip_by_envelope(&tile).unwrap();
+        assert_eq!(true, clipped.equals(&expected).unwrap());
+        let diagonal = GGeom::new("LINESTRING (0 0, 10 10)").unwrap();
+        let clipped = line.clip_by_envelope(&diagonal).unwrap();
+        assert_eq!(true, clipped.equals(&expected).unwrap());
+
+        let square = GGeom::new("POLYGON ((5 5, 5 15, 15 15, 15 5, 5 5))").unwrap();
+        let clipped = square.clip_by_bbox(&bbox).unwrap();
+        assert_almost_eq(25., clipped.area().unwrap());
+        assert_eq!(
+            true,
+            line.clip_by_rect(20., 20., 30., 30.)
+                .unwrap()
+                .is_empty()
+                .unwrap()
+        );
+        assert!(
+            line.clip_by_envelope(&GGeom::new("POLYGON EMPTY").unwrap())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        let origin = GGeom::new("POINT (0 0)").unwrap();
+        let point = GGeom::new("POINT (3 4)").unwrap();
+        let square = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        assert_almost_eq(5., origin.distance(&point).unwrap());
+        assert_eq!(0., square.distance(&point).unwrap());
+
+        let g1 = GGeom::new("LINESTRING (0 0, 100 0, 10 100, 10 100)").unwrap();
+        let g2 = GGeom::new("LINESTRING (0 100, 0 10, 80 10)").unwrap();
+        assert_almost_eq(22.360679774997898, g1.hausdorff_distance(&g2).unwrap());
+        assert_almost_eq(47.8, g1.hausdorff_distance_densify(&g2, 0.001).unwrap());
+        assert!(g1.hausdorff_distance_densify(&g2, 2.).is_err());
+    }
+
+    #[test]
+    #[cfg(geos_v3_7_0)]
+    fn test_frechet_distance() {
+        let g1 = GGeom::new("LINESTRING (0 0, 100 0)").unwrap();
+        let g2 = GGeom::new("LINESTRING (0 0, 50 50, 100 0)").unwrap();
+        assert_almost_eq(70.71067811865476, g1.frechet_distance(&g2).unwrap());
+        assert_almost_eq(50., g1.frechet_distance_densify(&g2, 0.5).unwrap());
+    }
+
+    #[test]
+    #[cfg(geos_v3_10_0)]
+    fn test_is_within_distance() {
+        let origin = GGeom::new("POINT (0 0)").unwrap();
+        let point = GGeom::new("POINT (3 4)").unwrap();
+        assert_eq!(true, origin.is_within_distance(&point, 5.).unwrap());
+        assert_eq!(false, origin.is_within_distance(&point, 4.9).unwrap());
+    }
+
+    #[test]
+    fn test_nearest_points() {
+        let road = GGeom::new("LINESTRING (0 0, 10 0)").unwrap();
+        let address = GGeom::new("POINT (4 3)").unwrap();
+        let points = road.nearest_points(&address).unwrap();
+        assert_eq!(4., points.get_x(0).unwrap());
+        assert_eq!(0., points.get_y(0).unwrap());
+        assert_eq!(4., points.get_x(1).unwrap());
+        assert_eq!(3., points.get_y(1).unwrap());
+        assert!(
+            road.nearest_points(&GGeom::new("POINT EMPTY").unwrap())
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(geos_v3_9_0)]
+    fn test_prepared_nearest_points() {
+        let road = GGeom::new("LINESTRING (0 0, 10 0)").unwrap();
+        let prepared = PreparedGGeom::new(&road).unwrap();
+        let points = prepared
+            .nearest_points(&GGeom::new("POINT (12 -1)").unwrap())
+            .unwrap();
+        assert_eq!(10., points.get_x(0).unwrap());
+        assert_eq!(0., points.get_y(0).unwrap());
+        assert_eq!(12., points.get_x(1).unwrap());
+        assert_eq!(-1., points.get_y(1).unwrap());
     }
 
     #[test]
     fn test_prepared_geoms() {
-        let g1 = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))");
-        let g2 = GGeom::new("POLYGON ((1 1, 1 3, 5 5, 5 0, 1 1))");
-        let pg1 = PreparedGGeom::new(&g1);
-        assert_eq!(true, pg1.intersects(&g2));
-        assert_eq!(true, pg1.contains(&g2.get_centroid()));
+        let g1 = GGeom::new("POLYGON ((0 0, 0 5, 5 5, 5 0, 0 0))").unwrap();
+        let g2 = GGeom::new("POLYGON ((1 1, 1 3, 5 5, 5 0, 1 1))").unwrap();
+        let pg1 = PreparedGGeom::new(&g1).unwrap();
+        assert_eq!(true, pg1.intersects(&g2).unwrap());
+        assert_eq!(true, pg1.contains(&g2.get_centroid().unwrap()).unwrap());
         let vec_geoms = vec![
-            GGeom::new("POINT (1.3 2.4)"),
-            GGeom::new("POINT (2.1 0.3)"),
-            GGeom::new("POINT (3.1 4.7)"),
-            GGeom::new("POINT (0.4 4.1)"),
+            GGeom::new("POINT (1.3 2.4)").unwrap(),
+            GGeom::new("POINT (2.1 0.3)").unwrap(),
+            GGeom::new("POINT (3.1 4.7)").unwrap(),
+            GGeom::new("POINT (0.4 4.1)").unwrap(),
         ];
         for geom in &vec_geoms {
-            assert_eq!(true, pg1.intersects(&geom));
+            assert_eq!(true, pg1.intersects(&geom).unwrap());
         }
+        drop(pg1);
+        // The source geometry outlives its prepared versions.
+        let pg2 = g1.to_prepared().unwrap();
+        assert_eq!(true, pg2.covers(&vec_geoms[0]).unwrap());
+        assert_eq!(false, pg2.disjoint(&g2).unwrap());
     }
 
     #[test]
     fn test_wkt_rounding_precision() {
-        let g = GGeom::new("LINESTRING(0.0 0.0, 7.0 7.0, 45.0 50.5, 100.0 100.0)");
-        let wkt = g.to_wkt_precison(Some(0));
+        let g = GGeom::new("LINESTRING(0.0 0.0, 7.0 7.0, 45.0 50.5, 100.0 100.0)").unwrap();
+        let wkt = g.to_wkt_precision(0).unwrap();
         assert_eq!(true, wkt == "LINESTRING (0 0, 7 7, 45 50, 100 100)");
-        let wkt2 = g.to_wkt();
+        let wkt2 = g.to_wkt().unwrap();
         assert!(wkt2 != wkt);
     }
 
+    #[test]
+    fn test_display_and_debug() {
+        let g = GGeom::new("POINT (2.4 3.25)").unwrap();
+        assert_eq!(g.to_wkt().unwrap(), format!("{}", g));
+        assert_eq!(g.to_wkt_precision(1).unwrap(), format!("{:.1}", g));
+        assert_eq!("POINT (2 3)", format!("{:.0}", g));
+        assert_eq!("    POINT (2.4 3.25)", format!("{:>20}", g));
+        assert_eq!("**POINT (2 3)**", format!("{:*^15.0}", g));
+        assert_eq!("POINT (2 3)  |", format!("{:<13.0}|", g));
+        assert_eq!(
+            format!("GGeom {{ type: \"Point\", wkt: {:?} }}", g.to_wkt().unwrap()),
+            format!("{:?}", g)
+        );
+    }
+
+    #[test]
+    fn test_wkt_writer_and_reader() {
+        let reader = WKTReader::new().unwrap();
+        let point = reader.read("POINT (1.5 2.3333 3)").unwrap();
+        let line = reader.read("LINESTRING (0 0, 7 7)").unwrap();
+        assert!(reader.read("POINT (1.5").is_err());
+
+        let writer = WKTWriter::new()
+            .unwrap()
+            .trim(true)
+            .output_dimension(OutputDimension::TwoD);
+        assert_eq!("LINESTRING (0 0, 7 7)", writer.write(&line).unwrap());
+        assert_eq!(OutputDimension::TwoD, writer.get_output_dimension());
+
+        let writer = writer.rounding_precision(Some(2));
+        assert_eq!("POINT (1.5 2.33)", writer.write(&point).unwrap());
+
+        let writer = writer.output_dimension(OutputDimension::ThreeD);
+        assert_eq!(OutputDimension::ThreeD, writer.get_output_dimension());
+        assert_eq!("POINT Z (1.5 2.33 3)", writer.write(&point).unwrap());
+        assert_eq!("LINESTRING (0 0, 7 7)", writer.write(&line).unwrap());
+
+        let writer = writer.old_3d(true);
+        assert_eq!("POINT (1.5 2.33 3)", writer.write(&point).unwrap());
+
+        let writer = writer.trim(false).output_dimension(OutputDimension::TwoD);
+        assert_eq!("POINT (1.50 2.33)", writer.write(&point).unwrap());
+
+        let writer = writer.trim(true).rounding_precision(None);
+        assert_eq!("POINT (1.5 2.3333)", writer.write(&point).unwrap());
+    }
+
     fn assert_almost_eq(a: f64, b: f64) {
         let f: f64 = a / b;
         assert!(f < 1.0001);
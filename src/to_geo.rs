@@ -0,0 +1,152 @@
+extern crate geo;
+
+use self::geo::{Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+                MultiPolygon, Point, Polygon};
+use std::convert::TryFrom;
+use error::GeosError;
+use ffi::{GeometryType, GGeom};
+
+fn points(g: &GGeom) -> Result<Vec<Point<f64>>, GeosError> {
+    let coords = g.get_coord_seq()?.to_vec()?;
+    Ok(coords.into_iter().map(|[x, y]| Point::new(x, y)).collect())
+}
+
+fn point(g: &GGeom) -> Result<Point<f64>, GeosError> {
+    match points(g)?.first() {
+        Some(pt) => Ok(*pt),
+        None => Err(GeosError::InvalidArgument(
+            "An empty Point can't be converted to a geo Point".to_string(),
+        )),
+    }
+}
+
+fn line_string(g: &GGeom) -> Result<LineString<f64>, GeosError> {
+    Ok(LineString(points(g)?))
+}
+
+fn polygon(g: &GGeom) -> Result<Polygon<f64>, GeosError> {
+    let mut rings = g.rings()?
+        .map(|ring| line_string(&ring))
+        .collect::<Result<Vec<_>, GeosError>>()?;
+    let exterior = rings.remove(0);
+    Ok(Polygon::new(exterior, rings))
+}
+
+fn parts<T, F>(g: &GGeom, convert: F) -> Result<Vec<T>, GeosError>
+where
+    F: Fn(&GGeom) -> Result<T, GeosError>,
+{
+    g.parts()?.map(|part| convert(&part)).collect()
+}
+
+fn geometry(g: &GGeom) -> Result<Geometry<f64>, GeosError> {
+    match g.geometry_type()? {
+        GeometryType::Point => Ok(Geometry::Point(point(g)?)),
+        GeometryType::LineString | GeometryType::LinearRing => {
+            Ok(Geometry::LineString(line_string(g)?))
+        }
+        GeometryType::Polygon => Ok(Geometry::Polygon(polygon(g)?)),
+        GeometryType::MultiPoint => Ok(Geometry::MultiPoint(MultiPoint(parts(g, point)?))),
+        GeometryType::MultiLineString => Ok(Geometry::MultiLineString(MultiLineString(parts(
+            g,
+            line_string,
+        )?))),
+        GeometryType::MultiPolygon => Ok(Geometry::MultiPolygon(MultiPolygon(parts(g, polygon)?))),
+        GeometryType::GeometryCollection => Ok(Geometry::GeometryCollection(
+            GeometryCollection(parts(g, geometry)?),
+        )),
+    }
+}
+
+impl TryFrom<&GGeom> for Geometry<f64> {
+    type Error = GeosError;
+
+    fn try_from(g: &GGeom) -> Result<Geometry<f64>, GeosError> {
+        geometry(g)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use to_geo::geo::{Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+                      MultiPolygon, Point, Polygon};
+    use ffi::GGeom;
+    use std::convert::TryFrom;
+
+    fn to_geo(wkt: &str) -> Geometry<f64> {
+        Geometry::try_from(&GGeom::new(wkt).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn point_and_lines_test() {
+        assert_eq!(Geometry::Point(Point::new(1., 2.)), to_geo("POINT (1 2)"));
+        assert!(Geometry::try_from(&GGeom::new("POINT EMPTY").unwrap()).is_err());
+
+        let line = LineString(vec![Point::new(0., 0.), Point::new(1., 0.), Point::new(1., 1.)]);
+        assert_eq!(
+            Geometry::LineString(line),
+            to_geo("LINESTRING (0 0, 1 0, 1 1)")
+        );
+
+        let ring = LineString(vec![
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 0.),
+        ]);
+        assert_eq!(
+            Geometry::LineString(ring),
+            to_geo("LINEARRING (0 0, 1 0, 1 1, 0 0)")
+        );
+    }
+
+    #[test]
+    fn polygon_test() {
+        let exterior = LineString(vec![
+            Point::new(0., 0.),
+            Point::new(0., 1.),
+            Point::new(1., 1.),
+            Point::new(1., 0.),
+            Point::new(0., 0.),
+        ]);
+        let interiors = vec![
+            LineString(vec![
+                Point::new(0.1, 0.1),
+                Point::new(0.1, 0.9),
+                Point::new(0.9, 0.9),
+                Point::new(0.9, 0.1),
+                Point::new(0.1, 0.1),
+            ]),
+        ];
+        let p = Polygon::new(exterior, interiors);
+
+        let geom = GGeom::try_from(&p).unwrap();
+        assert_eq!(Geometry::Polygon(p.clone()), Geometry::try_from(&geom).unwrap());
+
+        let mp = MultiPolygon(vec![p.clone(), p.clone()]);
+        let geom = GGeom::try_from(&mp).unwrap();
+        assert_eq!(Geometry::MultiPolygon(mp), Geometry::try_from(&geom).unwrap());
+    }
+
+    #[test]
+    fn collections_test() {
+        assert_eq!(
+            Geometry::MultiPoint(MultiPoint(vec![Point::new(0., 0.), Point::new(1., 1.)])),
+            to_geo("MULTIPOINT ((0 0), (1 1))")
+        );
+        assert_eq!(
+            Geometry::MultiLineString(MultiLineString(vec![
+                LineString(vec![Point::new(0., 0.), Point::new(1., 1.)]),
+                LineString(vec![Point::new(2., 2.), Point::new(3., 3.)]),
+            ])),
+            to_geo("MULTILINESTRING ((0 0, 1 1), (2 2, 3 3))")
+        );
+        assert_eq!(
+            Geometry::GeometryCollection(GeometryCollection(vec![
+                Geometry::Point(Point::new(1., 1.)),
+                Geometry::LineString(LineString(vec![Point::new(0., 0.), Point::new(1., 1.)])),
+            ])),
+            to_geo("GEOMETRYCOLLECTION (POINT (1 1), LINESTRING (0 0, 1 1))")
+        );
+    }
+}
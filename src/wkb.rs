@@ -0,0 +1,197 @@
+use libc::{c_char, c_int, c_void, size_t};
+use std::slice;
+use context_handle::{handle, last_error, with_drop_handle};
+use error::GeosError;
+use ffi::{GEOSFree_r, GEOSWKBReader, GEOSWKBReader_create_r, GEOSWKBReader_destroy_r,
+          GEOSWKBReader_readHEX_r, GEOSWKBReader_read_r, GEOSWKBWriter, GEOSWKBWriter_create_r,
+          GEOSWKBWriter_destroy_r, GEOSWKBWriter_getByteOrder_r,
+          GEOSWKBWriter_getIncludeSRID_r, GEOSWKBWriter_getOutputDimension_r,
+          GEOSWKBWriter_setByteOrder_r, GEOSWKBWriter_setIncludeSRID_r,
+          GEOSWKBWriter_setOutputDimension_r, GEOSWKBWriter_writeHEX_r, GEOSWKBWriter_write_r,
+          GGeom};
+
+/// Byte order of the WKB output, the machine's native byte order by default.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ByteOrder {
+    /// Most significant byte first (XDR).
+    BigEndian,
+    /// Least significant byte first (NDR).
+    LittleEndian,
+}
+
+/// Number of dimensions written for each coordinate.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OutputDimension {
+    /// Only X and Y are written, the default.
+    TwoD,
+    /// X, Y and Z are written, for the geometries having a Z coordinate.
+    ThreeD,
+}
+
+impl OutputDimension {
+    pub(crate) fn from_c_int(dimension: c_int) -> OutputDimension {
+        if dimension >= 3 {
+            OutputDimension::ThreeD
+        } else {
+            OutputDimension::TwoD
+        }
+    }
+
+    pub(crate) fn as_c_int(self) -> c_int {
+        match self {
+            OutputDimension::TwoD => 2,
+            OutputDimension::ThreeD => 3,
+        }
+    }
+}
+
+/// Writes geometries as WKB, or as EWKB (the PostGIS flavour) when the SRID is included.
+///
+/// ```no_run
+/// # use geos::{ByteOrder, GGeom, GeosError, OutputDimension, WKBWriter};
+/// # fn main() -> Result<(), GeosError> {
+/// let mut geom = GGeom::new("POINT (2.5 2.5 10)")?;
+/// geom.set_srid(4326);
+/// let writer = WKBWriter::new()?
+///     .byte_order(ByteOrder::BigEndian)
+///     .output_dimension(OutputDimension::ThreeD)
+///     .include_srid(true);
+/// let ewkb = writer.write_hex(&geom)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct WKBWriter(*mut GEOSWKBWriter);
+
+unsafe impl Send for WKBWriter {}
+
+impl Drop for WKBWriter {
+    fn drop(&mut self) {
+        with_drop_handle(|handle| unsafe { GEOSWKBWriter_destroy_r(handle, self.0) });
+    }
+}
+
+impl WKBWriter {
+    pub fn new() -> Result<WKBWriter, GeosError> {
+        let writer = unsafe { GEOSWKBWriter_create_r(handle()) };
+        if writer.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSWKBWriter_create returned NULL".to_string(),
+            )));
+        }
+        Ok(WKBWriter(writer))
+    }
+
+    pub fn byte_order(self, byte_order: ByteOrder) -> WKBWriter {
+        let byte_order = match byte_order {
+            ByteOrder::BigEndian => 0,
+            ByteOrder::LittleEndian => 1,
+        };
+        unsafe { GEOSWKBWriter_setByteOrder_r(handle(), self.0, byte_order) };
+        self
+    }
+
+    pub fn output_dimension(self, dimension: OutputDimension) -> WKBWriter {
+        unsafe { GEOSWKBWriter_setOutputDimension_r(handle(), self.0, dimension.as_c_int()) };
+        self
+    }
+
+    pub fn include_srid(self, include_srid: bool) -> WKBWriter {
+        unsafe { GEOSWKBWriter_setIncludeSRID_r(handle(), self.0, include_srid as c_char) };
+        self
+    }
+
+    pub fn get_byte_order(&self) -> ByteOrder {
+        match unsafe { GEOSWKBWriter_getByteOrder_r(handle(), self.0) } {
+            0 => ByteOrder::BigEndian,
+            _ => ByteOrder::LittleEndian,
+        }
+    }
+
+    pub fn get_output_dimension(&self) -> OutputDimension {
+        OutputDimension::from_c_int(unsafe {
+            GEOSWKBWriter_getOutputDimension_r(handle(), self.0)
+        })
+    }
+
+    pub fn get_include_srid(&self) -> bool {
+        unsafe { GEOSWKBWriter_getIncludeSRID_r(handle(), self.0) != 0 }
+    }
+
+    pub fn write(&self, g: &GGeom) -> Result<Vec<u8>, GeosError> {
+        let mut size: size_t = 0;
+        let wkb =
+            unsafe { GEOSWKBWriter_write_r(handle(), self.0, g.c_obj as *const c_void, &mut size) };
+        take_buffer(wkb, size, "GEOSWKBWriter_write")
+    }
+
+    /// Writes the geometry as hex-encoded WKB, with upper case digits.
+    pub fn write_hex(&self, g: &GGeom) -> Result<String, GeosError> {
+        let mut size: size_t = 0;
+        let hex = unsafe {
+            GEOSWKBWriter_writeHEX_r(handle(), self.0, g.c_obj as *const c_void, &mut size)
+        };
+        let hex = take_buffer(hex, size, "GEOSWKBWriter_writeHEX")?;
+        String::from_utf8(hex).map_err(|_| {
+            GeosError::GenericError("GEOSWKBWriter_writeHEX returned invalid UTF-8".to_string())
+        })
+    }
+}
+
+/// Copies a buffer allocated by GEOS and frees it.
+fn take_buffer(buf: *mut u8, size: size_t, func: &str) -> Result<Vec<u8>, GeosError> {
+    if buf.is_null() {
+        return Err(last_error(GeosError::NullReturn(format!(
+            "{} returned NULL",
+            func
+        ))));
+    }
+    let result = unsafe { slice::from_raw_parts(buf, size).to_vec() };
+    unsafe { GEOSFree_r(handle(), buf as *mut c_void) };
+    Ok(result)
+}
+
+/// Reads geometries from WKB or EWKB, given either as binary or hex-encoded.
+pub struct WKBReader(*mut GEOSWKBReader);
+
+unsafe impl Send for WKBReader {}
+
+impl Drop for WKBReader {
+    fn drop(&mut self) {
+        with_drop_handle(|handle| unsafe { GEOSWKBReader_destroy_r(handle, self.0) });
+    }
+}
+
+impl WKBReader {
+    pub fn new() -> Result<WKBReader, GeosError> {
+        let reader = unsafe { GEOSWKBReader_create_r(handle()) };
+        if reader.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSWKBReader_create returned NULL".to_string(),
+            )));
+        }
+        Ok(WKBReader(reader))
+    }
+
+    pub fn read(&self, wkb: &[u8]) -> Result<GGeom, GeosError> {
+        let obj = unsafe {
+            GEOSWKBReader_read_r(handle(), self.0, wkb.as_ptr(), wkb.len() as size_t)
+        };
+        if obj.is_null() {
+            return Err(last_error(GeosError::ParseError("invalid WKB".to_string())));
+        }
+        GGeom::new_from_c_obj(obj)
+    }
+
+    /// Reads hex-encoded WKB, with upper or lower case digits.
+    pub fn read_hex(&self, hex: &str) -> Result<GGeom, GeosError> {
+        let obj = unsafe {
+            GEOSWKBReader_readHEX_r(handle(), self.0, hex.as_ptr(), hex.len() as size_t)
+        };
+        if obj.is_null() {
+            return Err(last_error(GeosError::ParseError(
+                "invalid hex WKB".to_string(),
+            )));
+        }
+        GGeom::new_from_c_obj(obj)
+    }
+}
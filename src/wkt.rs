@@ -0,0 +1,131 @@
+use libc::{c_char, c_int, c_void};
+use std::ffi::CString;
+use context_handle::{handle, last_error, with_drop_handle};
+use error::GeosError;
+use ffi::{GEOSFree_r, GEOSWKTReader, GEOSWKTReader_create_r, GEOSWKTReader_destroy_r,
+          GEOSWKTReader_read_r, GEOSWKTWriter, GEOSWKTWriter_create_r, GEOSWKTWriter_destroy_r,
+          GEOSWKTWriter_getOutputDimension_r, GEOSWKTWriter_setOld3D_r,
+          GEOSWKTWriter_setOutputDimension_r, GEOSWKTWriter_setRoundingPrecision_r,
+          GEOSWKTWriter_setTrim_r, GEOSWKTWriter_write_r, GGeom, _string};
+use wkb::OutputDimension;
+
+/// Reads geometries from WKT, reusable to avoid creating a GEOS reader for each geometry.
+pub struct WKTReader(*mut GEOSWKTReader);
+
+unsafe impl Send for WKTReader {}
+
+impl Drop for WKTReader {
+    fn drop(&mut self) {
+        with_drop_handle(|handle| unsafe { GEOSWKTReader_destroy_r(handle, self.0) });
+    }
+}
+
+impl WKTReader {
+    pub fn new() -> Result<WKTReader, GeosError> {
+        let reader = unsafe { GEOSWKTReader_create_r(handle()) };
+        if reader.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSWKTReader_create returned NULL".to_string(),
+            )));
+        }
+        Ok(WKTReader(reader))
+    }
+
+    pub fn read(&self, wkt: &str) -> Result<GGeom, GeosError> {
+        let c_str = match CString::new(wkt) {
+            Ok(c_str) => c_str,
+            Err(_) => {
+                return Err(GeosError::InvalidArgument(
+                    "WKT string contains a nul byte".to_string(),
+                ))
+            }
+        };
+        let obj = unsafe { GEOSWKTReader_read_r(handle(), self.0, c_str.as_ptr()) };
+        if obj.is_null() {
+            return Err(last_error(GeosError::ParseError("invalid WKT".to_string())));
+        }
+        GGeom::new_from_c_obj(obj)
+    }
+}
+
+/// Writes geometries as WKT.
+///
+/// ```no_run
+/// # use geos::{GGeom, GeosError, OutputDimension, WKTWriter};
+/// # fn main() -> Result<(), GeosError> {
+/// let writer = WKTWriter::new()?
+///     .trim(true)
+///     .rounding_precision(Some(2))
+///     .output_dimension(OutputDimension::ThreeD);
+/// // Prints "POINT Z (2.5 2.33 10)"
+/// println!("{}", writer.write(&GGeom::new("POINT (2.5 2.3333 10)")?)?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct WKTWriter(*mut GEOSWKTWriter);
+
+unsafe impl Send for WKTWriter {}
+
+impl Drop for WKTWriter {
+    fn drop(&mut self) {
+        with_drop_handle(|handle| unsafe { GEOSWKTWriter_destroy_r(handle, self.0) });
+    }
+}
+
+impl WKTWriter {
+    pub fn new() -> Result<WKTWriter, GeosError> {
+        let writer = unsafe { GEOSWKTWriter_create_r(handle()) };
+        if writer.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSWKTWriter_create returned NULL".to_string(),
+            )));
+        }
+        Ok(WKTWriter(writer))
+    }
+
+    /// Removes the trailing zeros of the coordinates.
+    pub fn trim(self, trim: bool) -> WKTWriter {
+        unsafe { GEOSWKTWriter_setTrim_r(handle(), self.0, trim as c_char) };
+        self
+    }
+
+    /// Rounds the coordinates to `precision` decimals, or writes them in full
+    /// with `None`, the default.
+    pub fn rounding_precision(self, precision: Option<u32>) -> WKTWriter {
+        let precision = match precision {
+            Some(precision) => precision as c_int,
+            None => -1,
+        };
+        unsafe { GEOSWKTWriter_setRoundingPrecision_r(handle(), self.0, precision) };
+        self
+    }
+
+    pub fn output_dimension(self, dimension: OutputDimension) -> WKTWriter {
+        unsafe { GEOSWKTWriter_setOutputDimension_r(handle(), self.0, dimension.as_c_int()) };
+        self
+    }
+
+    /// Writes 3D geometries as `POINT (1 2 3)` instead of `POINT Z (1 2 3)`.
+    pub fn old_3d(self, use_old_3d: bool) -> WKTWriter {
+        unsafe { GEOSWKTWriter_setOld3D_r(handle(), self.0, use_old_3d as c_int) };
+        self
+    }
+
+    pub fn get_output_dimension(&self) -> OutputDimension {
+        OutputDimension::from_c_int(unsafe {
+            GEOSWKTWriter_getOutputDimension_r(handle(), self.0)
+        })
+    }
+
+    pub fn write(&self, g: &GGeom) -> Result<String, GeosError> {
+        let c_result = unsafe { GEOSWKTWriter_write_r(handle(), self.0, g.c_obj as *const c_void) };
+        if c_result.is_null() {
+            return Err(last_error(GeosError::NullReturn(
+                "GEOSWKTWriter_write returned NULL".to_string(),
+            )));
+        }
+        let result = _string(c_result);
+        unsafe { GEOSFree_r(handle(), c_result as *mut c_void) };
+        Ok(result)
+    }
+}